@@ -4,6 +4,10 @@ extern crate directories;
 
 use bencher::Bencher;
 use bencher::black_box;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
 use directories::BaseDirs;
 use directories::ProjectDirs;
 use directories::UserDirs;
@@ -20,9 +24,62 @@ fn user_dirs(b: &mut Bencher) {
     });
 }
 
+// Measures repeated `UserDirs::new()` calls with an existing user-dirs.dirs file,
+// which is only parsed again after it has been modified.
+fn user_dirs_with_file(b: &mut Bencher) {
+    with_user_dirs_files(1, |_| {
+        b.iter(|| {
+            let _ = black_box(UserDirs::new());
+        });
+    });
+}
+
+// Measures the same calls as `user_dirs_with_file` when the cache cannot be used,
+// as `$XDG_CONFIG_HOME` alternates between two directories, so that the file is parsed on every call.
+fn user_dirs_with_file_uncached(b: &mut Bencher) {
+    with_user_dirs_files(2, |config_dirs| {
+        let mut index = 0;
+        b.iter(|| {
+            index = (index + 1) % config_dirs.len();
+            env::set_var("XDG_CONFIG_HOME", &config_dirs[index]);
+            let _ = black_box(UserDirs::new());
+        });
+    });
+}
+
+// Creates `count` config directories containing a user-dirs.dirs file, and runs `f` with `$XDG_CONFIG_HOME` pointing to the first one.
+fn with_user_dirs_files<F: FnOnce(&[PathBuf])>(count: usize, f: F) {
+    let config_dirs: Vec<PathBuf> = (0..count).map(|index| env::temp_dir().join(format!("directories-bench-{}-{}", process::id(), index))).collect();
+    for config_dir in &config_dirs {
+        fs::create_dir_all(config_dir).unwrap();
+        fs::write(config_dir.join("user-dirs.dirs"), USER_DIRS_FILE).unwrap();
+    }
+    let previous = env::var_os("XDG_CONFIG_HOME");
+    env::set_var("XDG_CONFIG_HOME", &config_dirs[0]);
+    f(&config_dirs);
+    match previous {
+        Some(previous) => env::set_var("XDG_CONFIG_HOME", previous),
+        None           => env::remove_var("XDG_CONFIG_HOME")
+    }
+    for config_dir in &config_dirs {
+        let _ = fs::remove_dir_all(config_dir);
+    }
+}
+
+const USER_DIRS_FILE: &str = r#"
+XDG_DESKTOP_DIR="$HOME/Desktop"
+XDG_DOWNLOAD_DIR="$HOME/Downloads"
+XDG_TEMPLATES_DIR="$HOME/Templates"
+XDG_PUBLICSHARE_DIR="$HOME/Public"
+XDG_DOCUMENTS_DIR="$HOME/Documents"
+XDG_MUSIC_DIR="$HOME/Music"
+XDG_PICTURES_DIR="$HOME/Pictures"
+XDG_VIDEOS_DIR="$HOME/Videos"
+"#;
+
 fn project_dirs_from_path(b: &mut Bencher) {
     b.iter(|| {
-        let _ = black_box(ProjectDirs::from_path(Default::default()));
+        let _ = black_box(ProjectDirs::from_path(PathBuf::from("bar-app")));
     });
 }
 
@@ -32,10 +89,27 @@ fn project_dirs(b: &mut Bencher) {
     });
 }
 
+fn base_dirs_project(b: &mut Bencher) {
+    let base_dirs = BaseDirs::new().unwrap();
+    b.iter(|| {
+        let _ = black_box(base_dirs.project("org", "foo", "Bar App"));
+    });
+}
+
+fn global_base_dirs(b: &mut Bencher) {
+    b.iter(|| {
+        let _ = black_box(directories::global().base_dirs().cloned());
+    });
+}
+
 benchmark_group!(constructors,
     base_dirs,
     user_dirs,
+    user_dirs_with_file,
+    user_dirs_with_file_uncached,
     project_dirs_from_path,
     project_dirs,
+    base_dirs_project,
+    global_base_dirs,
 );
 benchmark_main!(constructors);
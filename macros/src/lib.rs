@@ -0,0 +1,166 @@
+//! Procedural macros for the _directories_ crate.
+//!
+//! This crate is an implementation detail of _directories_; enable its `macros` feature
+//! and use the re-exported macros instead of depending on this crate directly.
+
+#![deny(missing_docs)]
+
+extern crate proc_macro;
+extern crate toml;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use proc_macro::TokenStream;
+
+/// Creates a `ProjectDirs` struct from the metadata of the Cargo package being compiled.
+///
+/// The application name is taken from `CARGO_PKG_NAME`, the qualifier and organization from the
+/// optional `qualifier` and `organization` keys of the package's `package.metadata.directories` table:
+///
+/// ```toml
+/// [package.metadata.directories]
+/// qualifier    = "com"
+/// organization = "FooCorp"
+/// ```
+///
+/// The macro expands to a call of `directories::ProjectDirs::from` and has the same type, `Option<ProjectDirs>`.
+///
+/// Compilation fails if a name is rejected by `directories::AppId::from_parts`,
+/// or if it would be adapted differently on some platform, e. g. because it contains whitespace
+/// (replaced by `-` on macOS, kept on Windows) or uppercase letters in the application name (lowercased on Linux only).
+#[proc_macro]
+pub fn project_dirs(input: TokenStream) -> TokenStream {
+    if !input.is_empty() {
+        return compile_error("`project_dirs!` does not take any arguments");
+    }
+    match expand() {
+        Ok(tokens)   => tokens,
+        Err(message) => compile_error(&message)
+    }
+}
+
+fn expand() -> Result<TokenStream, String> {
+    let application  = env::var("CARGO_PKG_NAME").map_err(|_| "`CARGO_PKG_NAME` is not set, `project_dirs!` must be compiled by Cargo".to_string())?;
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").map_err(|_| "`CARGO_MANIFEST_DIR` is not set, `project_dirs!` must be compiled by Cargo".to_string())?;
+    let manifest     = Path::new(&manifest_dir).join("Cargo.toml");
+    let contents     = fs::read_to_string(&manifest).map_err(|e| format!("failed to read {}: {}", manifest.display(), e))?;
+    let metadata     = parse_metadata(&contents)?;
+
+    validate_qualifier(&metadata.qualifier)?;
+    validate_name("organization", &metadata.organization)?;
+    validate_name("application", &application)?;
+    if application.chars().any(char::is_uppercase) {
+        return Err(format!("application name {:?} contains uppercase letters, which are lowercased on Linux only", application));
+    }
+
+    // Including the manifest makes the compiler track it, so that changes to the metadata are picked up.
+    let expansion = format!(
+        "{{ const _: &[u8] = include_bytes!({:?}); ::directories::ProjectDirs::from({:?}, {:?}, {:?}) }}",
+        manifest.to_string_lossy(), metadata.qualifier, metadata.organization, application
+    );
+    expansion.parse().map_err(|_| "failed to expand `project_dirs!`".to_string())
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    format!("compile_error!({:?})", format!("project_dirs!: {}", message)).parse().unwrap()
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Metadata {
+    qualifier:    String,
+    organization: String
+}
+
+/// Extracts the keys of the `package.metadata.directories` table from a manifest.
+///
+/// The table may be written in any form TOML allows, e. g. as a `[package.metadata.directories]` section,
+/// as an inline table or with dotted keys.
+fn parse_metadata(manifest: &str) -> Result<Metadata, String> {
+    let manifest = manifest.parse::<toml::Value>().map_err(|e| format!("failed to parse Cargo.toml: {}", e))?;
+    let table    = match manifest.get("package").and_then(|package| package.get("metadata")).and_then(|metadata| metadata.get("directories")) {
+        Some(toml::Value::Table(table)) => table,
+        Some(_)                         => return Err("`package.metadata.directories` in Cargo.toml must be a table".to_string()),
+        None                            => return Ok(Metadata::default())
+    };
+    let mut metadata = Metadata::default();
+    for (key, value) in table {
+        let value = match value {
+            toml::Value::String(value) => value.clone(),
+            _                          => return Err(format!("value of `{}` in `package.metadata.directories` must be a string", key))
+        };
+        match key.as_str() {
+            "qualifier"    => metadata.qualifier = value,
+            "organization" => metadata.organization = value,
+            _              => return Err(format!("unknown key `{}` in `package.metadata.directories`, expected `qualifier` or `organization`", key))
+        }
+    }
+    Ok(metadata)
+}
+
+// The following rules mirror `AppId::from_parts` and the project path computation of each platform in the directories crate.
+
+fn validate_qualifier(qualifier: &str) -> Result<(), String> {
+    let is_label = |label: &str| !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if qualifier.is_empty() || qualifier.split('.').all(is_label) {
+        Ok(())
+    } else {
+        Err(format!("qualifier {:?} is not a sequence of dot-separated labels consisting of ASCII letters, digits and hyphens", qualifier))
+    }
+}
+
+fn validate_name(kind: &str, name: &str) -> Result<(), String> {
+    if let Some(c) = name.chars().find(|&c| c.is_control() || "./\\<>:\"|?*".contains(c)) {
+        return Err(format!("{} name {:?} contains {:?}, which is not allowed", kind, name, c));
+    }
+    if name.chars().any(char::is_whitespace) {
+        return Err(format!("{} name {:?} contains whitespace, which is adapted differently on Linux, macOS and Windows", kind, name));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_metadata, validate_name, validate_qualifier, Metadata};
+
+    #[test]
+    fn test_parse_metadata() {
+        let manifest = r#"
+[package]
+name = "bar-app"
+
+[package.metadata.directories]
+# the reverse domain name of Foo Corp
+qualifier    = "com"  # trailing comment
+organization = 'FooCorp'
+
+[dependencies]
+organization = "ignored"
+"#;
+        let expected = Metadata { qualifier: "com".to_string(), organization: "FooCorp".to_string() };
+        assert_eq!(Ok(expected), parse_metadata(manifest));
+
+        assert_eq!(Ok(Metadata::default()), parse_metadata("[package]\nname = \"bar-app\"\n"));
+        assert_eq!(Ok("Foo\"Corp".to_string()), parse_metadata("[package.metadata.directories]\norganization = \"Foo\\u0022Corp\"").map(|m| m.organization));
+        assert!(parse_metadata("[package.metadata.directories]\napplication = \"bar\"").is_err());
+        assert!(parse_metadata("[package.metadata.directories]\nqualifier = 1").is_err());
+        assert!(parse_metadata("[package.metadata.directories]\nqualifier = \"com\" trailing").is_err());
+
+        let expected = Metadata { qualifier: "com".to_string(), organization: "FooCorp".to_string() };
+        assert_eq!(Ok(expected), parse_metadata("[package.metadata]\ndirectories = { qualifier = \"com\", organization = \"FooCorp\" }"));
+        let expected = Metadata { qualifier: "com".to_string(), organization: String::new() };
+        assert_eq!(Ok(expected), parse_metadata("[package]\nname = \"bar-app\"\nmetadata.directories.qualifier = \"com\""));
+        assert!(parse_metadata("[package.metadata]\ndirectories = \"com\"").is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate_qualifier("").is_ok());
+        assert!(validate_qualifier("uk.co").is_ok());
+        assert!(validate_qualifier("uk..co").is_err());
+        assert!(validate_name("organization", "FooCorp").is_ok());
+        assert!(validate_name("organization", "Foo Corp").is_err());
+        assert!(validate_name("application", "bar/app").is_err());
+    }
+}
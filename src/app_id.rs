@@ -0,0 +1,295 @@
+use std::error;
+use std::fmt;
+use std::iter::FromIterator;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use sys;
+
+/// `AppId` is a validated application identifier in reverse domain name notation,
+/// consisting of a qualifier, an organization and an application name.
+///
+/// It renders the forms that the supported platforms use to name the directories of an application,
+/// and can be passed to [`ProjectDirs::from_app_id`](struct.ProjectDirs.html#method.from_app_id).
+///
+/// # Examples
+///
+/// ```
+/// use directories::AppId;
+/// let app_id = AppId::from_parts("com", "Foo Corp", "Bar App").unwrap();
+/// assert_eq!(app_id.linux_name(), "barapp");
+/// assert_eq!(app_id.bundle_id(),  "com.Foo-Corp.Bar-App");
+/// assert_eq!(app_id.flatpak_id(), Some("com.FooCorp.BarApp".to_string()));
+///
+/// let app_id: AppId = "com.example.BarApp".parse().unwrap();
+/// assert_eq!(app_id.organization(), "example");
+/// assert_eq!(app_id.application(),  "BarApp");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AppId {
+    qualifier:    String,
+    organization: String,
+    application:  String
+}
+
+/// The reason why a value was rejected by [`AppId::parse`] or [`AppId::from_parts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AppIdError {
+    /// The application name is empty or consists only of whitespace.
+    EmptyApplication,
+    /// The qualifier is not a sequence of dot-separated labels consisting of ASCII letters, digits and hyphens.
+    InvalidQualifier,
+    /// The organization name consists only of whitespace, contains a dot, a path separator, a control character
+    /// or one of the characters reserved on Windows (`<>:"|?*`), or is a name reserved for devices on Windows, e. g. `CON`.
+    InvalidOrganization,
+    /// The application name contains a dot, a path separator, a control character
+    /// or one of the characters reserved on Windows (`<>:"|?*`), or is a name reserved for devices on Windows, e. g. `CON`.
+    InvalidApplication
+}
+
+impl AppId {
+    /// Parses an application identifier in reverse domain name notation, like `"com.example.BarApp"`.
+    ///
+    /// The last dot-separated part is the application name, the part before it is the organization name,
+    /// and all remaining parts form the qualifier. `"BarApp"` and `"example.BarApp"` are accepted as well,
+    /// leaving the qualifier and the organization empty.
+    pub fn parse(id: &str) -> Result<AppId, AppIdError> {
+        let mut parts = id.rsplitn(3, '.');
+        let application  = parts.next().unwrap_or("");
+        let organization = parts.next().unwrap_or("");
+        let qualifier    = parts.next().unwrap_or("");
+        AppId::from_parts(qualifier, organization, application)
+    }
+    /// Creates an application identifier from its parts, which have the same meaning as the parameters of
+    /// [`ProjectDirs::from`](struct.ProjectDirs.html#method.from).
+    ///
+    /// The qualifier and the organization may be empty, the application name must not.
+    pub fn from_parts(qualifier: &str, organization: &str, application: &str) -> Result<AppId, AppIdError> {
+        if !qualifier.is_empty() && !qualifier.split('.').all(is_qualifier_label) {
+            return Err(AppIdError::InvalidQualifier);
+        }
+        if !organization.is_empty() && (organization.trim().is_empty() || !organization.chars().all(is_name_char) || is_reserved_on_windows(organization.trim())) {
+            return Err(AppIdError::InvalidOrganization);
+        }
+        if application.trim().is_empty() {
+            return Err(AppIdError::EmptyApplication);
+        }
+        if !application.chars().all(is_name_char) || is_reserved_on_windows(application.trim()) {
+            return Err(AppIdError::InvalidApplication);
+        }
+        let app_id = AppId {
+            qualifier:    qualifier.to_string(),
+            organization: organization.to_string(),
+            application:  application.to_string()
+        };
+        Ok(app_id)
+    }
+    /// Returns the qualifier, e. g. `"com"`.
+    pub fn qualifier(&self) -> &str {
+        &self.qualifier
+    }
+    /// Returns the organization name, e. g. `"Foo Corp"`.
+    pub fn organization(&self) -> &str {
+        &self.organization
+    }
+    /// Returns the application name, e. g. `"Bar App"`.
+    pub fn application(&self) -> &str {
+        &self.application
+    }
+    /// Returns the name of the application suitable for displaying it to users, e. g. `"Bar App"`.
+    pub fn display_name(&self) -> &str {
+        self.application.trim()
+    }
+    /// Returns the name of the application suitable for use as a file name on all platforms, e. g. `"Bar-App"`.
+    pub fn file_name(&self) -> String {
+        trim_then_replace_spaces(&self.application, "-")
+    }
+    /// Returns the name used for the project path on Linux, e. g. `"barapp"`.
+    pub fn linux_name(&self) -> String {
+        trim_and_lowercase_then_replace_spaces(&self.application, "")
+    }
+    /// Returns the bundle identifier used for the project path on macOS, e. g. `"com.Foo-Corp.Bar-App"`.
+    pub fn bundle_id(&self) -> String {
+        bundle_id(&self.qualifier, &self.organization, &self.application)
+    }
+    /// Returns the project path used on Windows, e. g. `"Foo Corp\Bar App"`.
+    pub fn windows_path(&self) -> PathBuf {
+        PathBuf::from_iter(&[&self.organization, &self.application])
+    }
+    /// Returns the application ID used by Flatpak, e. g. `"com.FooCorp.BarApp"`.
+    ///
+    /// Whitespace is removed from the organization and application names, and characters not allowed by Flatpak are replaced by underscores.
+    /// Flatpak only allows `-` in the last part, so it is kept in the application name and replaced in the other parts.
+    /// Returns `None` if the identifier lacks a qualifier or an organization, as Flatpak requires at least three parts.
+    pub fn flatpak_id(&self) -> Option<String> {
+        if self.qualifier.is_empty() || self.organization.is_empty() {
+            return None;
+        }
+        let mut parts: Vec<String> = self.qualifier.split('.').map(|label| flatpak_part(label, false)).collect();
+        parts.push(flatpak_part(&self.organization, false));
+        parts.push(flatpak_part(&self.application, true));
+        Some(parts.join("."))
+    }
+    /// Returns the project path used on the platform the library is running on.
+    ///
+    /// This is the value [`ProjectDirs::project_path`](struct.ProjectDirs.html#method.project_path) returns
+    /// for a `ProjectDirs` struct created from this identifier.
+    pub fn project_path(&self) -> PathBuf {
+        sys::project_path_from(&self.qualifier, &self.organization, &self.application)
+    }
+}
+
+impl fmt::Display for AppId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = vec![&self.qualifier, &self.organization, &self.application]; parts.retain(|e| !e.is_empty());
+        f.write_str(&parts.iter().map(|e| e.as_str()).collect::<Vec<_>>().join("."))
+    }
+}
+
+impl FromStr for AppId {
+    type Err = AppIdError;
+
+    fn from_str(id: &str) -> Result<AppId, AppIdError> {
+        AppId::parse(id)
+    }
+}
+
+impl fmt::Display for AppIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            AppIdError::EmptyApplication    => "application name is empty",
+            AppIdError::InvalidQualifier    => "qualifier is not a valid reverse domain name",
+            AppIdError::InvalidOrganization => "organization name is blank or contains a character which is not allowed",
+            AppIdError::InvalidApplication  => "application name contains a character which is not allowed"
+        };
+        f.write_str(message)
+    }
+}
+
+impl error::Error for AppIdError {}
+
+fn is_qualifier_label(label: &str) -> bool {
+    !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn is_name_char(c: char) -> bool {
+    !c.is_control() && !"./\\<>:\"|?*".contains(c)
+}
+
+// Returns whether `name` refers to a device on Windows, which is the case for the reserved names with any extension.
+pub(crate) fn is_reserved_on_windows(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim_end().to_ascii_uppercase();
+    match stem.as_str() {
+        "CON" | "PRN" | "AUX" | "NUL" => true,
+        _                             => stem.len() == 4 && (stem.starts_with("COM") || stem.starts_with("LPT")) && stem.ends_with(|c: char| ('1'..='9').contains(&c))
+    }
+}
+
+fn flatpak_part(name: &str, allow_hyphen: bool) -> String {
+    let mut part: String = name.split_whitespace().collect::<String>().chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || (allow_hyphen && c == '-') { c } else { '_' })
+        .collect();
+    if part.starts_with(|c: char| c.is_ascii_digit()) {
+        part.insert(0, '_');
+    }
+    part
+}
+
+pub(crate) fn bundle_id(qualifier: &str, organization: &str, application: &str) -> String {
+    // we should replace more characters, according to RFC1034 identifier rules
+    let organization = organization.replace(" ", "-");
+    let application  = application.replace(" ", "-");
+    let mut parts    = vec![qualifier, &organization, &application]; parts.retain(|e| !e.is_empty());
+    parts.join(".")
+}
+
+pub(crate) fn trim_and_lowercase_then_replace_spaces(name: &str, replacement: &str) -> String {
+    let mut buf = String::with_capacity(name.len());
+    let mut parts = name.split_whitespace();
+    let mut current_part = parts.next();
+    let replace = !replacement.is_empty();
+    while current_part.is_some() {
+        let value = current_part.unwrap().to_lowercase();
+        buf.push_str(&value);
+        current_part = parts.next();
+        if replace && current_part.is_some() {
+            buf.push_str(replacement);
+        }
+    }
+    buf
+}
+
+pub(crate) fn trim_then_replace_spaces(name: &str, replacement: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(replacement)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use app_id::trim_and_lowercase_then_replace_spaces;
+    use AppId;
+    use AppIdError;
+
+    #[test]
+    fn test_trim_and_lowercase_then_replace_spaces() {
+        let input1    = "Bar App";
+        let actual1   = trim_and_lowercase_then_replace_spaces(input1, "-");
+        let expected1 = "bar-app";
+        assert_eq!(expected1, actual1);
+
+        let input2    = "BarApp-Foo";
+        let actual2   = trim_and_lowercase_then_replace_spaces(input2, "-");
+        let expected2 = "barapp-foo";
+        assert_eq!(expected2, actual2);
+
+        let input3    = " Bar App ";
+        let actual3   = trim_and_lowercase_then_replace_spaces(input3, "-");
+        let expected3 = "bar-app";
+        assert_eq!(expected3, actual3);
+
+        let input4    = "  Bar  App  ";
+        let actual4   = trim_and_lowercase_then_replace_spaces(input4, "-");
+        let expected4 = "bar-app";
+        assert_eq!(expected4, actual4);
+    }
+
+    #[test]
+    fn test_app_id_parse() {
+        let app_id = AppId::parse("com.example.BarApp").unwrap();
+        assert_eq!(("com", "example", "BarApp"), (app_id.qualifier(), app_id.organization(), app_id.application()));
+        assert_eq!("com.example.BarApp", app_id.to_string());
+
+        let app_id = AppId::parse("uk.co.example.BarApp").unwrap();
+        assert_eq!(("uk.co", "example", "BarApp"), (app_id.qualifier(), app_id.organization(), app_id.application()));
+
+        let app_id = AppId::parse("BarApp").unwrap();
+        assert_eq!(("", "", "BarApp"), (app_id.qualifier(), app_id.organization(), app_id.application()));
+
+        assert_eq!(Err(AppIdError::EmptyApplication),    AppId::parse("com.example."));
+        assert_eq!(Err(AppIdError::InvalidQualifier),    AppId::parse("com..example.BarApp"));
+        assert_eq!(Err(AppIdError::InvalidOrganization), AppId::from_parts("com", "..", "BarApp"));
+        assert_eq!(Err(AppIdError::InvalidApplication),  AppId::from_parts("com", "Foo Corp", "Bar/App"));
+        assert_eq!(Err(AppIdError::InvalidApplication),  AppId::parse("com.Foo.CON"));
+        assert_eq!(Err(AppIdError::InvalidApplication),  AppId::from_parts("com", "Foo Corp", " lpt1 "));
+        assert_eq!(Err(AppIdError::InvalidOrganization), AppId::from_parts("com", "Nul", "BarApp"));
+        assert!(AppId::from_parts("com", "Foo Corp", "CONSOLE").is_ok());
+    }
+
+    #[test]
+    fn test_app_id_forms() {
+        let app_id = AppId::from_parts("com", "Foo Corp", "Bar App").unwrap();
+        assert_eq!("Bar App",                            app_id.display_name());
+        assert_eq!("Bar-App",                            app_id.file_name());
+        assert_eq!("barapp",                             app_id.linux_name());
+        assert_eq!("com.Foo-Corp.Bar-App",               app_id.bundle_id());
+        assert_eq!(PathBuf::from("Foo Corp").join("Bar App"), app_id.windows_path());
+        assert_eq!(Some("com.FooCorp.BarApp".to_string()), app_id.flatpak_id());
+
+        let app_id = AppId::from_parts("io", "3D Labs", "Bar-App").unwrap();
+        assert_eq!(Some("io._3DLabs.Bar-App".to_string()), app_id.flatpak_id());
+        let app_id = AppId::from_parts("my-company.io", "3D-Labs", "Bar-App").unwrap();
+        assert_eq!(Some("my_company.io._3D_Labs.Bar-App".to_string()), app_id.flatpak_id());
+        assert_eq!(None, AppId::from_parts("", "", "BarApp").unwrap().flatpak_id());
+    }
+}
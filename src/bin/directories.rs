@@ -0,0 +1,310 @@
+// Prints the directories resolved by this library, so that shell scripts and packaging recipes
+// can use the same paths as Rust code instead of hard-coding them.
+
+extern crate directories;
+
+use std::env;
+use std::ffi::OsString;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::process;
+
+use directories::BaseDirs;
+use directories::Platform;
+use directories::ProjectDirs;
+use directories::Shell;
+use directories::UserDirs;
+
+const USAGE: &str = "\
+Usage: directories [OPTIONS] [ENTRY]...
+
+Prints the directories resolved by the directories crate.
+
+Without --app, the entries of BaseDirs and UserDirs are printed, otherwise the entries of ProjectDirs.
+ENTRY restricts the output to the given entries, named like the getters, e. g. config_dir or project_path.
+If a single ENTRY is given and the format is text, only its path is printed.
+
+Options:
+      --qualifier <QUALIFIER>  The qualifier of the project, e. g. com
+      --org <ORGANIZATION>     The organization of the project, e. g. Foo Corp
+      --app <APPLICATION>      The application name of the project, e. g. Bar App
+      --format <FORMAT>        The output format: text (default), json or env (quoted for POSIX shells)
+      --platform <PLATFORM>    Print the default layout of another platform: linux, macos or windows
+  -h, --help                   Print this help
+  -V, --version                Print the version
+";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Env
+}
+
+struct Options {
+    qualifier:    String,
+    organization: String,
+    application:  Option<String>,
+    format:       Format,
+    platform:     Platform,
+    entries:      Vec<String>
+}
+
+fn main() {
+    let options = match parse_args(env::args_os().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None)          => return,
+        Err(message)      => {
+            eprintln!("directories: {}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(message) = run(&options) {
+        eprintln!("directories: {}", message);
+        process::exit(1);
+    }
+}
+
+// `env::args` would panic on arguments which are not valid Unicode, so they are reported as a usage error instead.
+fn parse_args<I: Iterator<Item = OsString>>(args: I) -> Result<Option<Options>, String> {
+    let mut args = args.map(|arg| arg.into_string().map_err(|arg| format!("argument `{}` is not valid Unicode", arg.to_string_lossy())));
+    let mut options = Options {
+        qualifier:    String::new(),
+        organization: String::new(),
+        application:  None,
+        format:       Format::Text,
+        platform:     Platform::current(),
+        entries:      Vec::new()
+    };
+    while let Some(arg) = args.next() {
+        let arg = arg?;
+        let (name, inline_value) = match arg.find('=') {
+            Some(index) if arg.starts_with("--") => (arg[..index].to_string(), Some(arg[index + 1..].to_string())),
+            _                                     => (arg.clone(), None)
+        };
+        let mut value = || match inline_value.clone() {
+            Some(value) => Ok(value),
+            None        => args.next().unwrap_or_else(|| Err(format!("missing value for `{}`", name)))
+        };
+        match name.as_str() {
+            "-h" | "--help"    => { print!("{}", USAGE); return Ok(None); },
+            "-V" | "--version" => { println!("directories {}", env!("CARGO_PKG_VERSION")); return Ok(None); },
+            "--qualifier"      => options.qualifier = value()?,
+            "--org"            => options.organization = value()?,
+            "--app"            => options.application = Some(value()?),
+            "--format"         => options.format = match value()?.as_str() {
+                "text" => Format::Text,
+                "json" => Format::Json,
+                "env"  => Format::Env,
+                other  => return Err(format!("unknown format `{}`", other))
+            },
+            "--platform"       => options.platform = match value()?.as_str() {
+                "linux"   => Platform::Linux,
+                "macos"   => Platform::MacOs,
+                "windows" => Platform::Windows,
+                other     => return Err(format!("unknown platform `{}`", other))
+            },
+            _ if name.starts_with('-') => return Err(format!("unknown option `{}`", name)),
+            _                          => options.entries.push(arg)
+        }
+    }
+    if options.application.is_none() && !(options.qualifier.is_empty() && options.organization.is_empty()) {
+        return Err("`--qualifier` and `--org` require `--app`".to_string());
+    }
+    Ok(Some(options))
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let no_home_dir = || "no valid home directory path could be retrieved from the operating system".to_string();
+    let (base_dirs, user_dirs) = if options.platform == Platform::current() {
+        (BaseDirs::new().ok_or_else(no_home_dir)?, UserDirs::new().ok_or_else(no_home_dir)?)
+    } else {
+        let home_dir = BaseDirs::new().ok_or_else(no_home_dir)?.home_dir().to_path_buf();
+        (options.platform.base_dirs(&home_dir), options.platform.user_dirs(&home_dir))
+    };
+
+    let proj_dirs;
+    let all_entries = match options.application {
+        Some(ref application) => {
+            proj_dirs = options.platform.project(&base_dirs, &options.qualifier, &options.organization, application).map_err(|e| e.to_string())?;
+            project_entries(&proj_dirs)
+        },
+        None => {
+            let mut entries = base_entries(&base_dirs);
+            entries.extend(user_entries(&user_dirs).into_iter().filter(|&(name, _)| name != "home_dir"));
+            entries
+        }
+    };
+
+    let entries = if options.entries.is_empty() {
+        all_entries
+    } else {
+        let mut entries = Vec::new();
+        for name in &options.entries {
+            match all_entries.iter().find(|&&(entry, _)| entry == name) {
+                Some(&entry) => entries.push(entry),
+                None         => return Err(format!("unknown entry `{}`", name))
+            }
+        }
+        entries
+    };
+
+    let output = if options.format == Format::Text && options.entries.len() == 1 {
+        match entries[0] {
+            (_,    Some(path)) => format!("{}\n", path.display()),
+            (name, None)       => return Err(format!("`{}` is not available on {}", name, options.platform))
+        }
+    } else {
+        format_entries(&entries, options.format)?
+    };
+    io::stdout().write_all(output.as_bytes()).map_err(|e| e.to_string())
+}
+
+fn format_entries(entries: &[(&'static str, Option<&Path>)], format: Format) -> Result<String, String> {
+    let mut output = String::new();
+    match format {
+        Format::Text => for &(name, path) in entries {
+            if let Some(path) = path {
+                output.push_str(&format!("{:<16} {}\n", name, path.display()));
+            }
+        },
+        Format::Json => {
+            output.push('{');
+            for (index, &(name, path)) in entries.iter().enumerate() {
+                let value = match path.map(|path| path.to_str()) {
+                    Some(Some(path)) => json_string(path),
+                    Some(None)       => return Err(format!("`{}` is not valid Unicode and cannot be represented in JSON", name)),
+                    None             => "null".to_string()
+                };
+                output.push_str(&format!("{}\n  \"{}\": {}", if index == 0 { "" } else { "," }, name, value));
+            }
+            output.push_str(if entries.is_empty() { "}\n" } else { "\n}\n" });
+        },
+        // The values are quoted, so that the output can be `eval`'d or sourced by shell scripts.
+        Format::Env => for &(name, path) in entries {
+            if let Some(path) = path {
+                let value = Shell::Posix.quote(path).map_err(|e| format!("`{}`: {}", name, e))?;
+                output.push_str(&format!("{}={}\n", name.to_uppercase(), value));
+            }
+        }
+    }
+    Ok(output)
+}
+
+fn json_string(value: &str) -> String {
+    let mut string = String::with_capacity(value.len() + 2);
+    string.push('"');
+    for c in value.chars() {
+        match c {
+            '"'  => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            c if (c as u32) < 0x20 => string.push_str(&format!("\\u{:04x}", c as u32)),
+            c    => string.push(c)
+        }
+    }
+    string.push('"');
+    string
+}
+
+fn base_entries(base_dirs: &BaseDirs) -> Vec<(&'static str, Option<&Path>)> {
+    vec![
+        ("home_dir",         Some(base_dirs.home_dir())),
+        ("cache_dir",        Some(base_dirs.cache_dir())),
+        ("config_dir",       Some(base_dirs.config_dir())),
+        ("config_local_dir", Some(base_dirs.config_local_dir())),
+        ("data_dir",         Some(base_dirs.data_dir())),
+        ("data_local_dir",   Some(base_dirs.data_local_dir())),
+        ("executable_dir",   base_dirs.executable_dir()),
+        ("preference_dir",   Some(base_dirs.preference_dir())),
+        ("runtime_dir",      base_dirs.runtime_dir()),
+        ("state_dir",        base_dirs.state_dir())
+    ]
+}
+
+fn user_entries(user_dirs: &UserDirs) -> Vec<(&'static str, Option<&Path>)> {
+    vec![
+        ("home_dir",     Some(user_dirs.home_dir())),
+        ("audio_dir",    user_dirs.audio_dir()),
+        ("desktop_dir",  user_dirs.desktop_dir()),
+        ("document_dir", user_dirs.document_dir()),
+        ("download_dir", user_dirs.download_dir()),
+        ("font_dir",     user_dirs.font_dir()),
+        ("picture_dir",  user_dirs.picture_dir()),
+        ("public_dir",   user_dirs.public_dir()),
+        ("template_dir", user_dirs.template_dir()),
+        ("video_dir",    user_dirs.video_dir())
+    ]
+}
+
+fn project_entries(proj_dirs: &ProjectDirs) -> Vec<(&'static str, Option<&Path>)> {
+    vec![
+        ("project_path",     Some(proj_dirs.project_path())),
+        ("cache_dir",        Some(proj_dirs.cache_dir())),
+        ("config_dir",       Some(proj_dirs.config_dir())),
+        ("config_local_dir", Some(proj_dirs.config_local_dir())),
+        ("data_dir",         Some(proj_dirs.data_dir())),
+        ("data_local_dir",   Some(proj_dirs.data_local_dir())),
+        ("preference_dir",   Some(proj_dirs.preference_dir())),
+        ("runtime_dir",      proj_dirs.runtime_dir()),
+        ("state_dir",        proj_dirs.state_dir())
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Option<Options>, String> {
+        parse_args(args.iter().map(OsString::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = args(&["--app", "Bar App", "--org=Foo Corp", "--format", "env", "--platform", "windows", "config_dir"]).unwrap().unwrap();
+        assert_eq!(options.application, Some("Bar App".to_string()));
+        assert_eq!(options.organization, "Foo Corp");
+        assert!(options.format == Format::Env && options.platform == Platform::Windows);
+        assert_eq!(options.entries, vec!["config_dir".to_string()]);
+
+        assert!(args(&["--org", "Foo Corp"]).is_err());
+        assert!(args(&["--format", "yaml"]).is_err());
+        assert!(args(&["--app"]).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_parse_args_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let args = vec![OsString::from("--app"), OsStr::from_bytes(b"Bar\xff").to_os_string()];
+        assert_eq!(parse_args(args.into_iter()).err(), Some("argument `Bar\u{fffd}` is not valid Unicode".to_string()));
+    }
+
+    #[test]
+    fn test_format_entries() {
+        let entries = vec![("config_dir", Some(Path::new("/home/alice/.config/\"bar\""))), ("runtime_dir", None)];
+        assert_eq!(format_entries(&entries, Format::Json).unwrap(), "{\n  \"config_dir\": \"/home/alice/.config/\\\"bar\\\"\",\n  \"runtime_dir\": null\n}\n");
+        assert_eq!(format_entries(&entries, Format::Env).unwrap(), "CONFIG_DIR='/home/alice/.config/\"bar\"'\n");
+
+        let entries = vec![("data_dir", Some(Path::new("/Users/Alice's $HOME/`x`/Application Support")))];
+        assert_eq!(format_entries(&entries, Format::Env).unwrap(), "DATA_DIR='/Users/Alice'\\''s $HOME/`x`/Application Support'\n");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_format_entries_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let entries = vec![("data_dir", Some(Path::new(OsStr::from_bytes(b"/home/alice/\xff"))))];
+        assert!(format_entries(&entries, Format::Json).is_err());
+        assert_eq!(format_entries(&entries, Format::Env).unwrap(), "DATA_DIR='/home/alice/'\"$(printf '\\377')\"\n");
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use probe;
+use BaseDirs;
+use DirKind;
+use Platform;
+use UserDirs;
+
+impl BaseDirs {
+    /// Returns the kind of the most specific directory of this struct which contains `path`, and the path relative to it.
+    ///
+    /// If several directories share the same path, e. g. the config and preference directories on Linux,
+    /// the first one in the order of [`DirKind::ALL`] is returned.
+    ///
+    /// Paths are compared component by component, ignoring case on Windows and macOS.
+    /// If `path` is not located in any of the directories as given, symlinks in `path` and in the directories are resolved,
+    /// so that e. g. a path in the target of a symlinked config directory is still classified as `DirKind::Config`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use directories::{DirKind, Platform};
+    ///
+    /// let base_dirs = Platform::Linux.base_dirs(Path::new("/home/alice"));
+    /// let (kind, remainder) = base_dirs.classify(Path::new("/home/alice/.cache/barapp/index")).unwrap();
+    /// assert_eq!(kind, DirKind::Cache);
+    /// assert_eq!(remainder, Path::new("barapp/index"));
+    /// ```
+    pub fn classify(&self, path: &Path) -> Option<(DirKind, PathBuf)> {
+        classify(&self.entries(), path, Platform::current())
+    }
+}
+
+impl UserDirs {
+    /// Returns the kind of the most specific directory of this struct which contains `path`, and the path relative to it.
+    ///
+    /// See [`BaseDirs::classify`] for details.
+    pub fn classify(&self, path: &Path) -> Option<(DirKind, PathBuf)> {
+        classify(&self.entries(), path, Platform::current())
+    }
+}
+
+pub(crate) fn classify(entries: &[(DirKind, Option<&Path>)], path: &Path, platform: Platform) -> Option<(DirKind, PathBuf)> {
+    if let Some(classified) = most_specific(entries, path, platform) {
+        return Some(classified);
+    }
+    let path     = canonicalize(path)?;
+    let resolved = entries.iter().map(|&(kind, dir)| (kind, dir.and_then(canonicalize))).collect::<Vec<_>>();
+    let resolved = resolved.iter().map(|&(kind, ref dir)| (kind, dir.as_ref().map(PathBuf::as_path))).collect::<Vec<_>>();
+    most_specific(&resolved, &path, platform)
+}
+
+fn most_specific(entries: &[(DirKind, Option<&Path>)], path: &Path, platform: Platform) -> Option<(DirKind, PathBuf)> {
+    let mut best: Option<(usize, DirKind, PathBuf)> = None;
+    for &(kind, dir) in entries {
+        if let Some(dir) = dir {
+            let depth = dir.components().count();
+            if best.as_ref().map_or(false, |&(best_depth, _, _)| depth <= best_depth) {
+                continue;
+            }
+            if let Some(remainder) = strip_prefix(path, dir, platform) {
+                best = Some((depth, kind, remainder));
+            }
+        }
+    }
+    best.map(|(_, kind, remainder)| (kind, remainder))
+}
+
+// Like `Path::strip_prefix`, but ignoring case on the platforms whose filesystems are case-insensitive by default.
+pub(crate) fn strip_prefix(path: &Path, prefix: &Path, platform: Platform) -> Option<PathBuf> {
+    let mut components = path.components();
+    for prefix_component in prefix.components() {
+        match components.next() {
+            Some(component) if is_same_component(component, prefix_component, platform) => {},
+            _                                                                             => return None
+        }
+    }
+    Some(components.as_path().to_path_buf())
+}
+
+fn is_same_component(a: Component, b: Component, platform: Platform) -> bool {
+    match platform {
+        Platform::Linux                     => a == b,
+        Platform::MacOs | Platform::Windows => a.as_os_str().to_string_lossy().to_lowercase() == b.as_os_str().to_string_lossy().to_lowercase()
+    }
+}
+
+// Resolves the symlinks in the nearest existing ancestor of `path`, as the remaining components cannot be symlinks.
+fn canonicalize(path: &Path) -> Option<PathBuf> {
+    let ancestor = probe::nearest_existing(path)?;
+    let resolved = fs::canonicalize(ancestor).ok()?;
+    Some(resolved.join(path.strip_prefix(ancestor).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use super::classify;
+    use DirKind;
+    use Platform;
+
+    #[test]
+    fn test_classify() {
+        let base_dirs = Platform::Linux.base_dirs(Path::new("/home/alice"));
+        let entries   = base_dirs.entries();
+        assert_eq!(classify(&entries, Path::new("/home/alice/.config/barapp/a.toml"), Platform::Linux), Some((DirKind::Config, PathBuf::from("barapp/a.toml"))));
+        assert_eq!(classify(&entries, Path::new("/home/alice/.local/share"), Platform::Linux),          Some((DirKind::Data, PathBuf::new())));
+        assert_eq!(classify(&entries, Path::new("/home/alice/notes"), Platform::Linux),                 Some((DirKind::Home, PathBuf::from("notes"))));
+        assert_eq!(classify(&entries, Path::new("/home/Alice/.cache"), Platform::Linux),                None);
+        assert_eq!(classify(&entries, Path::new("/home/Alice/.CACHE/x"), Platform::MacOs),              Some((DirKind::Cache, PathBuf::from("x"))));
+
+        let user_dirs = Platform::Windows.user_dirs(Path::new("/Users/Alice"));
+        let entries   = user_dirs.entries();
+        assert_eq!(classify(&entries, Path::new("/users/alice/documents/a.txt"), Platform::Windows), Some((DirKind::Document, PathBuf::from("a.txt"))));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_classify_symlinks() {
+        use std::fs;
+        use std::os::unix::fs::symlink;
+        use std::sync::Arc;
+
+        use test_util::TempDir;
+
+        let temp_dir = TempDir::new("classify");
+        fs::create_dir_all(temp_dir.join("home").join(".local")).unwrap();
+        fs::create_dir_all(temp_dir.join("cache")).unwrap();
+        symlink(temp_dir.join("cache"), temp_dir.join("home").join(".cache")).unwrap();
+
+        let mut base_dirs = Platform::Linux.base_dirs(&temp_dir.join("home"));
+        base_dirs.runtime_dir = Some(Arc::from(temp_dir.join("runtime")));
+        let entries = base_dirs.entries();
+        assert_eq!(classify(&entries, &temp_dir.join("cache").join("barapp"), Platform::Linux), Some((DirKind::Cache, PathBuf::from("barapp"))));
+        assert_eq!(classify(&entries, &temp_dir.join("runtime").join("barapp"), Platform::Linux), Some((DirKind::Runtime, PathBuf::from("barapp"))));
+        assert_eq!(classify(&entries, &temp_dir.join("other"), Platform::Linux), None);
+    }
+}
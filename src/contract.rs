@@ -0,0 +1,152 @@
+use std::path::Component;
+use std::path::Path;
+
+use classify::strip_prefix;
+use BaseDirs;
+use Platform;
+
+impl BaseDirs {
+    /// Rewrites `path` to its shortest symbolic form relative to the directories of this struct, for display.
+    ///
+    /// The symbolic forms are those understood by [`BaseDirs::expand`]:
+    ///
+    /// |Platform | Forms                                                                                                                | Example                               |
+    /// | ------- | -------------------------------------------------------------------------------------------------------------------- | ------------------------------------- |
+    /// | Linux   | `~`, `$XDG_CACHE_HOME`, `$XDG_CONFIG_HOME`, `$XDG_DATA_HOME`, `$XDG_STATE_HOME`, `$XDG_BIN_HOME`, `$XDG_RUNTIME_DIR` | ~/.config/barapp                      |
+    /// | macOS   | `~`                                                                                                                  | ~/Library/Caches/com.Foo-Corp.Bar-App |
+    /// | Windows | `~`, `%APPDATA%`, `%LOCALAPPDATA%`                                                                                   | %LOCALAPPDATA%\Foo Corp\Bar App\cache |
+    ///
+    /// If several forms apply, the shortest one is returned, so `$XDG_CONFIG_HOME` is only used if the config directory is
+    /// not located in the home directory. Paths outside of all directories are returned unchanged.
+    /// Like in [`BaseDirs::classify`], case is ignored on Windows and macOS.
+    /// Components which are not valid Unicode are converted lossily.
+    ///
+    /// For paths which are valid Unicode, `self.expand(&self.contract(path))` returns `path`,
+    /// unless the path contains the character `$` (`%` on Windows), which would be read as a variable reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use directories::Platform;
+    ///
+    /// let base_dirs = Platform::current().base_dirs(Path::new("/home/alice"));
+    /// let path      = base_dirs.home_dir().join("notes");
+    /// assert_eq!(base_dirs.contract(&path), format!("~{}notes", std::path::MAIN_SEPARATOR));
+    /// ```
+    pub fn contract(&self, path: &Path) -> String {
+        contract(self, path, Platform::current(), false)
+    }
+    /// Rewrites `path` like [`BaseDirs::contract`], and additionally removes information which may identify the user,
+    /// e. g. for telemetry.
+    ///
+    /// Components equal to the user name, i. e. the last component of the home directory, are replaced by `…`,
+    /// and paths outside of all directories are reduced to their root, e. g. `/…` for `/home/bob/notes`.
+    pub fn contract_redacted(&self, path: &Path) -> String {
+        contract(self, path, Platform::current(), true)
+    }
+}
+
+fn contract(base_dirs: &BaseDirs, path: &Path, platform: Platform, redact: bool) -> String {
+    let separator = if platform == Platform::Windows { "\\" } else { "/" };
+    let user_name = base_dirs.home_dir().file_name().map(|name| name.to_string_lossy());
+
+    // The more specific forms come first, so that they are preferred over `~` if both have the same length.
+    let roots: Vec<(&str, Option<&Path>)> = match platform {
+        Platform::Linux => vec![
+            ("$XDG_CACHE_HOME",  Some(base_dirs.cache_dir())),
+            ("$XDG_CONFIG_HOME", Some(base_dirs.config_dir())),
+            ("$XDG_DATA_HOME",   Some(base_dirs.data_dir())),
+            ("$XDG_STATE_HOME",  base_dirs.state_dir()),
+            ("$XDG_BIN_HOME",    base_dirs.executable_dir()),
+            ("$XDG_RUNTIME_DIR", base_dirs.runtime_dir()),
+            ("~",                Some(base_dirs.home_dir()))
+        ],
+        Platform::MacOs => vec![
+            ("~",                Some(base_dirs.home_dir()))
+        ],
+        Platform::Windows => vec![
+            ("%APPDATA%",        Some(base_dirs.data_dir())),
+            ("%LOCALAPPDATA%",   Some(base_dirs.data_local_dir())),
+            ("~",                Some(base_dirs.home_dir()))
+        ]
+    };
+
+    let mut shortest: Option<String> = None;
+    for &(symbol, root) in &roots {
+        if let Some(rest) = root.and_then(|root| strip_prefix(path, root, platform)) {
+            let mut contracted = symbol.to_string();
+            for component in rest.components() {
+                let component = component.as_os_str().to_string_lossy();
+                let is_user   = user_name.as_ref().map_or(false, |user_name| is_same_name(&component, user_name, platform));
+                contracted.push_str(separator);
+                contracted.push_str(if redact && is_user { "…" } else { &component });
+            }
+            if shortest.as_ref().map_or(true, |shortest| contracted.len() < shortest.len()) {
+                shortest = Some(contracted);
+            }
+        }
+    }
+
+    match shortest {
+        Some(contracted) => contracted,
+        None if redact   => {
+            let root: String = path.components()
+                .take_while(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            root + "…"
+        },
+        None             => path.to_string_lossy().into_owned()
+    }
+}
+
+// Compares names like `classify::strip_prefix` compares components.
+fn is_same_name(a: &str, b: &str, platform: Platform) -> bool {
+    match platform {
+        Platform::Linux                     => a == b,
+        Platform::MacOs | Platform::Windows => a.to_lowercase() == b.to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use super::contract;
+    use Platform;
+
+    #[test]
+    fn test_contract() {
+        let mut base_dirs = Platform::Linux.base_dirs(Path::new("/home/alice"));
+        assert_eq!(contract(&base_dirs, Path::new("/home/alice/.config/barapp"), Platform::Linux, false), "~/.config/barapp");
+        assert_eq!(contract(&base_dirs, Path::new("/home/alice"), Platform::Linux, false), "~");
+        assert_eq!(contract(&base_dirs, Path::new("/home/alicexyz"), Platform::Linux, false), "/home/alicexyz");
+        assert_eq!(contract(&base_dirs, Path::new("/home/alice/alice"), Platform::Linux, true), "~/…");
+        assert_eq!(contract(&base_dirs, Path::new("/home/bob/notes"), Platform::Linux, true), "/…");
+        assert_eq!(contract(&base_dirs, Path::new("/home/alice/Library/Caches"), Platform::MacOs, false), "~/Library/Caches");
+
+        base_dirs.config_dir = Arc::from(Path::new("/data/config"));
+        assert_eq!(contract(&base_dirs, Path::new("/data/config/barapp"), Platform::Linux, false), "$XDG_CONFIG_HOME/barapp");
+
+        // The case of the home directory and of the user name is ignored on macOS.
+        let base_dirs = Platform::MacOs.base_dirs(Path::new("/Users/Alice"));
+        assert_eq!(contract(&base_dirs, Path::new("/users/alice/Library/Caches"), Platform::MacOs, false), "~/Library/Caches");
+        assert_eq!(contract(&base_dirs, Path::new("/Users/Alice/Documents/ALICE"), Platform::MacOs, true), "~/Documents/…");
+        assert_eq!(contract(&base_dirs, Path::new("/Users/Alice/Documents/ALICE"), Platform::Linux, true), "~/Documents/ALICE");
+
+        let base_dirs = Platform::Windows.base_dirs(Path::new("/Users/Alice"));
+        let path: PathBuf = ["/Users/Alice", "AppData", "Local", "Foo Corp", "Bar App"].iter().collect();
+        assert_eq!(contract(&base_dirs, &path, Platform::Windows, false), "%LOCALAPPDATA%\\Foo Corp\\Bar App");
+    }
+
+    #[test]
+    fn test_contract_round_trip() {
+        let base_dirs = Platform::current().base_dirs(Path::new("/home/alice"));
+        for path in &[base_dirs.home_dir().join("notes"), base_dirs.config_dir().join("bar app"), PathBuf::from("/etc/hosts")] {
+            assert_eq!(&base_dirs.expand(&base_dirs.contract(path)).unwrap(), path);
+        }
+    }
+}
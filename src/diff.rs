@@ -0,0 +1,109 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use BaseDirs;
+use DirKind;
+use ProjectDirs;
+use UserDirs;
+
+/// `DirChange` describes a directory whose path differs between two `BaseDirs`, `UserDirs` or `ProjectDirs` structs.
+///
+/// It is returned by [`BaseDirs::diff`], [`UserDirs::diff`] and [`ProjectDirs::diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DirChange {
+    /// The kind of the directory.
+    pub kind: DirKind,
+    /// The path in the struct `diff` was invoked on, or `None` if the directory was not available.
+    pub old:  Option<PathBuf>,
+    /// The path in the struct passed to `diff`, or `None` if the directory is not available.
+    pub new:  Option<PathBuf>
+}
+
+impl BaseDirs {
+    /// Returns the directories whose paths differ between `self` and `other`, in the order of [`DirKind::ALL`].
+    ///
+    /// # Examples
+    ///
+    /// Detecting that the data directory was moved since the last run, given the `BaseDirs` struct persisted by it:
+    ///
+    /// ```
+    /// use directories::{BaseDirs, DirKind};
+    /// # fn load_last_seen() -> Option<BaseDirs> { BaseDirs::new() }
+    /// if let (Some(last_seen), Some(current)) = (load_last_seen(), BaseDirs::new()) {
+    ///     for change in last_seen.diff(&current) {
+    ///         if change.kind == DirKind::Data {
+    ///             // migrate files from change.old to change.new
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn diff(&self, other: &BaseDirs) -> Vec<DirChange> {
+        diff_entries(&self.entries(), &other.entries())
+    }
+}
+
+impl UserDirs {
+    /// Returns the directories whose paths differ between `self` and `other`, in the order of [`DirKind::ALL`].
+    pub fn diff(&self, other: &UserDirs) -> Vec<DirChange> {
+        diff_entries(&self.entries(), &other.entries())
+    }
+}
+
+impl ProjectDirs {
+    /// Returns the directories whose paths differ between `self` and `other`, in the order of [`DirKind::ALL`].
+    ///
+    /// The project path is not compared, as any change to it also changes the paths of all directories.
+    pub fn diff(&self, other: &ProjectDirs) -> Vec<DirChange> {
+        diff_entries(&self.entries(), &other.entries())
+    }
+}
+
+fn diff_entries(old: &[(DirKind, Option<&Path>)], new: &[(DirKind, Option<&Path>)]) -> Vec<DirChange> {
+    old.iter().zip(new)
+        .filter(|&(&(_, old), &(_, new))| old != new)
+        .map(|(&(kind, old), &(_, new))| DirChange { kind, old: old.map(Path::to_path_buf), new: new.map(Path::to_path_buf) })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use DirChange;
+    use DirKind;
+
+    fn base_dirs(data_dir: &str, runtime_dir: Option<&str>) -> ::BaseDirs {
+        let path = |p: &str| -> Arc<Path> { Arc::from(Path::new(p)) };
+        ::BaseDirs {
+            home_dir:         path("/home/alice"),
+            cache_dir:        path("/home/alice/.cache"),
+            config_dir:       path("/home/alice/.config"),
+            config_local_dir: path("/home/alice/.config"),
+            data_dir:         path(data_dir),
+            data_local_dir:   path(data_dir),
+            executable_dir:   Some(path("/home/alice/.local/bin")),
+            preference_dir:   path("/home/alice/.config"),
+            runtime_dir:      runtime_dir.map(path),
+            state_dir:        Some(path("/home/alice/.local/state"))
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = base_dirs("/home/alice/.local/share", Some("/run/user/1000"));
+        assert_eq!(old, old.clone());
+        assert!(old.diff(&old.clone()).is_empty());
+
+        let new = base_dirs("/data/alice", None);
+        assert!(old != new);
+        let expected = vec![
+            DirChange { kind: DirKind::Data,      old: Some(PathBuf::from("/home/alice/.local/share")), new: Some(PathBuf::from("/data/alice")) },
+            DirChange { kind: DirKind::DataLocal, old: Some(PathBuf::from("/home/alice/.local/share")), new: Some(PathBuf::from("/data/alice")) },
+            DirChange { kind: DirKind::Runtime,   old: Some(PathBuf::from("/run/user/1000")),           new: None }
+        ];
+        assert_eq!(expected, old.diff(&new));
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+use std::path::Path;
+
+use BaseDirs;
+use ProjectDirs;
+use Snapshot;
+use UserDirs;
+
+/// `DirKind` identifies one of the directories provided by `BaseDirs`, `UserDirs` and `ProjectDirs`.
+///
+/// Each variant corresponds to the getter of the same name, e. g. `DirKind::ConfigLocal` to `config_local_dir`.
+/// The variants `Cache` to `State` are provided by `BaseDirs` and `ProjectDirs`, the variants `Audio` to `Video` by `UserDirs`,
+/// and `Home` by `BaseDirs` and `UserDirs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DirKind {
+    /// The home directory, see [`BaseDirs::home_dir`](struct.BaseDirs.html#method.home_dir).
+    Home,
+    /// The cache directory, see [`BaseDirs::cache_dir`](struct.BaseDirs.html#method.cache_dir).
+    Cache,
+    /// The config directory, see [`BaseDirs::config_dir`](struct.BaseDirs.html#method.config_dir).
+    Config,
+    /// The local config directory, see [`BaseDirs::config_local_dir`](struct.BaseDirs.html#method.config_local_dir).
+    ConfigLocal,
+    /// The data directory, see [`BaseDirs::data_dir`](struct.BaseDirs.html#method.data_dir).
+    Data,
+    /// The local data directory, see [`BaseDirs::data_local_dir`](struct.BaseDirs.html#method.data_local_dir).
+    DataLocal,
+    /// The executable directory, see [`BaseDirs::executable_dir`](struct.BaseDirs.html#method.executable_dir).
+    Executable,
+    /// The preference directory, see [`BaseDirs::preference_dir`](struct.BaseDirs.html#method.preference_dir).
+    Preference,
+    /// The runtime directory, see [`BaseDirs::runtime_dir`](struct.BaseDirs.html#method.runtime_dir).
+    Runtime,
+    /// The state directory, see [`BaseDirs::state_dir`](struct.BaseDirs.html#method.state_dir).
+    State,
+    /// The audio directory, see [`UserDirs::audio_dir`](struct.UserDirs.html#method.audio_dir).
+    Audio,
+    /// The desktop directory, see [`UserDirs::desktop_dir`](struct.UserDirs.html#method.desktop_dir).
+    Desktop,
+    /// The document directory, see [`UserDirs::document_dir`](struct.UserDirs.html#method.document_dir).
+    Document,
+    /// The download directory, see [`UserDirs::download_dir`](struct.UserDirs.html#method.download_dir).
+    Download,
+    /// The font directory, see [`UserDirs::font_dir`](struct.UserDirs.html#method.font_dir).
+    Font,
+    /// The picture directory, see [`UserDirs::picture_dir`](struct.UserDirs.html#method.picture_dir).
+    Picture,
+    /// The public directory, see [`UserDirs::public_dir`](struct.UserDirs.html#method.public_dir).
+    Public,
+    /// The template directory, see [`UserDirs::template_dir`](struct.UserDirs.html#method.template_dir).
+    Template,
+    /// The video directory, see [`UserDirs::video_dir`](struct.UserDirs.html#method.video_dir).
+    Video
+}
+
+impl DirKind {
+    /// All variants, in declaration order.
+    pub const ALL: [DirKind; 19] = [
+        DirKind::Home,
+        DirKind::Cache, DirKind::Config, DirKind::ConfigLocal, DirKind::Data, DirKind::DataLocal,
+        DirKind::Executable, DirKind::Preference, DirKind::Runtime, DirKind::State,
+        DirKind::Audio, DirKind::Desktop, DirKind::Document, DirKind::Download, DirKind::Font,
+        DirKind::Picture, DirKind::Public, DirKind::Template, DirKind::Video
+    ];
+
+    /// Returns the name of the variant, e. g. `"ConfigLocal"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            DirKind::Home        => "Home",
+            DirKind::Cache       => "Cache",
+            DirKind::Config      => "Config",
+            DirKind::ConfigLocal => "ConfigLocal",
+            DirKind::Data        => "Data",
+            DirKind::DataLocal   => "DataLocal",
+            DirKind::Executable  => "Executable",
+            DirKind::Preference  => "Preference",
+            DirKind::Runtime     => "Runtime",
+            DirKind::State       => "State",
+            DirKind::Audio       => "Audio",
+            DirKind::Desktop     => "Desktop",
+            DirKind::Document    => "Document",
+            DirKind::Download    => "Download",
+            DirKind::Font        => "Font",
+            DirKind::Picture     => "Picture",
+            DirKind::Public      => "Public",
+            DirKind::Template    => "Template",
+            DirKind::Video       => "Video"
+        }
+    }
+    /// Returns the variant with the given name, e. g. `DirKind::ConfigLocal` for `"ConfigLocal"`.
+    pub fn from_name(name: &str) -> Option<DirKind> {
+        DirKind::ALL.iter().cloned().find(|kind| kind.name() == name)
+    }
+}
+
+/// `DirLookup` is implemented by the types which provide directories identified by a [`DirKind`].
+pub trait DirLookup {
+    /// Returns the path of the directory of the given kind,
+    /// or `None` if the directory is not provided by this type or not available on the platform.
+    fn dir(&self, kind: DirKind) -> Option<&Path>;
+}
+
+impl DirLookup for BaseDirs {
+    fn dir(&self, kind: DirKind) -> Option<&Path> {
+        self.entries().iter().find(|&&(k, _)| k == kind).and_then(|&(_, dir)| dir)
+    }
+}
+
+impl DirLookup for UserDirs {
+    fn dir(&self, kind: DirKind) -> Option<&Path> {
+        self.entries().iter().find(|&&(k, _)| k == kind).and_then(|&(_, dir)| dir)
+    }
+}
+
+impl DirLookup for ProjectDirs {
+    fn dir(&self, kind: DirKind) -> Option<&Path> {
+        self.entries().iter().find(|&&(k, _)| k == kind).and_then(|&(_, dir)| dir)
+    }
+}
+
+/// Directories are looked up in the `BaseDirs` struct of the snapshot first, then in its `UserDirs` struct.
+impl DirLookup for Snapshot {
+    fn dir(&self, kind: DirKind) -> Option<&Path> {
+        self.base_dirs().and_then(|base_dirs| base_dirs.dir(kind)).or_else(|| self.user_dirs().and_then(|user_dirs| user_dirs.dir(kind)))
+    }
+}
+
+impl fmt::Display for DirKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
@@ -0,0 +1,365 @@
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use probe;
+use DirKind;
+use FileSystem;
+use FileSystemKind;
+use Platform;
+use ProjectDirs;
+
+// Directories on filesystems with less space available than this are reported as `DirWarning::LowSpace`.
+const LOW_SPACE: u64 = 100 * 1024 * 1024;
+
+const XDG_VARIABLES: [&str; 6] = ["XDG_CACHE_HOME", "XDG_CONFIG_HOME", "XDG_DATA_HOME", "XDG_STATE_HOME", "XDG_BIN_HOME", "XDG_RUNTIME_DIR"];
+
+/// `Diagnosis` is the report returned by [`ProjectDirs::diagnose`].
+///
+/// Its `Display` implementation renders a human-readable report, e. g. for a `--doctor` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// The findings for each directory of the `ProjectDirs` struct, in the order of [`DirKind::ALL`].
+    /// Directories which are not available on the platform are omitted.
+    pub dirs: Vec<DirDiagnosis>,
+    /// The environment variables which are set, but were not used to compute the paths.
+    pub ignored_vars: Vec<IgnoredVar>
+}
+
+/// `DirDiagnosis` holds the findings for a single directory of a [`Diagnosis`].
+///
+/// If the directory does not exist, the checks of writability, filesystem and free space are applied to its nearest existing ancestor,
+/// as this is where the directory would be created.
+/// Fields which could not be determined on the platform are `None`; ownership and permissions are only available on Unix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirDiagnosis {
+    /// The kind of the directory.
+    pub kind: DirKind,
+    /// The path of the directory.
+    pub path: PathBuf,
+    /// Whether the path exists.
+    pub exists: bool,
+    /// Whether the path exists and is a directory, following symlinks.
+    pub is_dir: bool,
+    /// The path with all symlinks resolved, if it differs from `path`.
+    pub resolved_path: Option<PathBuf>,
+    /// Whether files can be created in the directory, or the directory can be created in its nearest existing ancestor.
+    pub writable: bool,
+    /// The user ID of the owner of the directory.
+    pub owner: Option<u32>,
+    /// Whether the directory is owned by the effective user of the current process.
+    pub owned_by_current_user: Option<bool>,
+    /// The permission bits of the directory, e. g. `0o700`.
+    pub mode: Option<u32>,
+    /// The filesystem the directory is located on.
+    pub file_system: Option<FileSystem>,
+    /// Whether that filesystem is mounted read-only.
+    pub read_only_mount: Option<bool>,
+    /// The space in bytes available to unprivileged users on that filesystem.
+    pub available_space: Option<u64>
+}
+
+/// `IgnoredVar` describes an environment variable which is set, but was not used to compute the paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoredVar {
+    /// The name of the variable, e. g. `"XDG_CONFIG_HOME"`.
+    pub name: &'static str,
+    /// The value of the variable.
+    pub value: OsString,
+    /// Why the variable was ignored.
+    pub reason: IgnoredVarReason
+}
+
+/// The reason why an environment variable was ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IgnoredVarReason {
+    /// The value is not an absolute path, which the XDG base directory specification requires.
+    RelativePath,
+    /// The variable is not used on this platform, e. g. `XDG_CONFIG_HOME` on macOS and Windows.
+    UnusedOnPlatform
+}
+
+/// A problem found by [`ProjectDirs::diagnose`], see [`DirDiagnosis::warnings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DirWarning {
+    /// The path exists, but is not a directory.
+    NotADirectory,
+    /// Files cannot be created in the directory, or the directory cannot be created.
+    NotWritable,
+    /// The directory is owned by another user.
+    NotOwnedByCurrentUser,
+    /// The directory is writable by all users.
+    WorldWritable,
+    /// The filesystem of the directory is mounted read-only.
+    ReadOnlyMount,
+    /// Less than 100 MiB are available on the filesystem of the directory.
+    LowSpace,
+    /// The directory is located on a network filesystem, which may lack reliable file locking,
+    /// and which the XDG base directory specification forbids for the runtime directory.
+    NetworkFileSystem,
+    /// The runtime directory is accessible by other users, which the XDG base directory specification forbids.
+    RuntimeDirNotPrivate
+}
+
+impl ProjectDirs {
+    /// Inspects the directories of this struct and the environment, and returns a report of the findings.
+    ///
+    /// The report covers, for each directory, whether it exists and is writable, its owner and permissions,
+    /// the filesystem it is located on and the space available there, and symlinks on its path.
+    /// It also lists `XDG_*` environment variables which were ignored, because they are set to relative paths
+    /// or are not used on the current platform.
+    ///
+    /// Neither the directories nor their ancestors are created or modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::ProjectDirs;
+    /// if let Some(proj_dirs) = ProjectDirs::from("com", "Foo Corp", "Bar App") {
+    ///     let diagnosis = proj_dirs.diagnose();
+    ///     if diagnosis.has_warnings() {
+    ///         eprintln!("{}", diagnosis);
+    ///     }
+    /// }
+    /// ```
+    pub fn diagnose(&self) -> Diagnosis {
+        let dirs = self.entries().iter()
+            .filter_map(|&(kind, path)| path.map(|path| diagnose_dir(kind, path)))
+            .collect();
+        Diagnosis {
+            dirs,
+            ignored_vars: ignored_vars()
+        }
+    }
+}
+
+impl Diagnosis {
+    /// Returns whether any directory has warnings or any environment variable was ignored.
+    pub fn has_warnings(&self) -> bool {
+        !self.ignored_vars.is_empty() || self.dirs.iter().any(|dir| !dir.warnings().is_empty())
+    }
+}
+
+impl DirDiagnosis {
+    /// Returns the problems found for this directory.
+    ///
+    /// A directory which does not exist yet is not a problem by itself, as applications usually create their directories on demand.
+    pub fn warnings(&self) -> Vec<DirWarning> {
+        let mut warnings = Vec::new();
+        if self.exists && !self.is_dir {
+            warnings.push(DirWarning::NotADirectory);
+        }
+        if !self.writable {
+            warnings.push(DirWarning::NotWritable);
+        }
+        if self.owned_by_current_user == Some(false) {
+            warnings.push(DirWarning::NotOwnedByCurrentUser);
+        }
+        if let Some(mode) = self.mode {
+            // The sticky bit prevents users from removing each other's files, as in /tmp.
+            if mode & 0o002 != 0 && mode & 0o1000 == 0 {
+                warnings.push(DirWarning::WorldWritable);
+            }
+            if self.kind == DirKind::Runtime && mode & 0o077 != 0 {
+                warnings.push(DirWarning::RuntimeDirNotPrivate);
+            }
+        }
+        if self.read_only_mount == Some(true) {
+            warnings.push(DirWarning::ReadOnlyMount);
+        }
+        if self.available_space.map_or(false, |space| space < LOW_SPACE) {
+            warnings.push(DirWarning::LowSpace);
+        }
+        if self.file_system.as_ref().map_or(false, |fs| fs.kind == FileSystemKind::Network) {
+            warnings.push(DirWarning::NetworkFileSystem);
+        }
+        warnings
+    }
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for dir in &self.dirs {
+            writeln!(f, "{:<11} {}", dir.kind, dir.path.display())?;
+            if let Some(ref resolved_path) = dir.resolved_path {
+                writeln!(f, "{:<11} -> {}", "", resolved_path.display())?;
+            }
+            let mut facts = vec![
+                if !dir.exists { "does not exist".to_string() } else if dir.is_dir { "exists".to_string() } else { "exists, not a directory".to_string() },
+                if dir.writable { "writable".to_string() } else { "not writable".to_string() }
+            ];
+            if let Some(owner) = dir.owner {
+                facts.push(format!("owner {}{}", owner, if dir.owned_by_current_user == Some(true) { " (current user)" } else { "" }));
+            }
+            if let Some(mode) = dir.mode {
+                facts.push(format!("mode {:04o}", mode));
+            }
+            if let Some(ref file_system) = dir.file_system {
+                facts.push(file_system.to_string());
+            }
+            if dir.read_only_mount == Some(true) {
+                facts.push("mounted read-only".to_string());
+            }
+            if let Some(available_space) = dir.available_space {
+                facts.push(format!("{} available", Bytes(available_space)));
+            }
+            writeln!(f, "{:<11} {}", "", facts.join(", "))?;
+            for warning in dir.warnings() {
+                writeln!(f, "{:<11} warning: {}", "", warning)?;
+            }
+        }
+        for var in &self.ignored_vars {
+            writeln!(f, "ignored: {}={} ({})", var.name, var.value.to_string_lossy(), var.reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DirWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            DirWarning::NotADirectory         => "the path is not a directory",
+            DirWarning::NotWritable           => "the directory is not writable",
+            DirWarning::NotOwnedByCurrentUser => "the directory is owned by another user",
+            DirWarning::WorldWritable         => "the directory is writable by all users",
+            DirWarning::ReadOnlyMount         => "the filesystem is mounted read-only",
+            DirWarning::LowSpace              => "less than 100 MiB are available",
+            DirWarning::NetworkFileSystem     => "the directory is located on a network filesystem",
+            DirWarning::RuntimeDirNotPrivate  => "the runtime directory is accessible by other users"
+        };
+        f.write_str(message)
+    }
+}
+
+impl fmt::Display for IgnoredVarReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            IgnoredVarReason::RelativePath     => "not an absolute path",
+            IgnoredVarReason::UnusedOnPlatform => "not used on this platform"
+        };
+        f.write_str(message)
+    }
+}
+
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit  = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit  += 1;
+        }
+        write!(f, "{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn diagnose_dir(kind: DirKind, path: &Path) -> DirDiagnosis {
+    let metadata      = fs::metadata(path).ok();
+    let exists        = fs::symlink_metadata(path).is_ok();
+    let resolved_path = fs::canonicalize(path).ok().filter(|resolved_path| resolved_path != path);
+    let probe_path    = probe::nearest_existing(path);
+    let capacity      = probe_path.and_then(probe::capacity);
+    let owner         = metadata.as_ref().and_then(owner);
+
+    DirDiagnosis {
+        kind,
+        path:                  path.to_path_buf(),
+        exists,
+        is_dir:                metadata.as_ref().map_or(false, fs::Metadata::is_dir),
+        resolved_path,
+        writable:              probe_path.map_or(false, probe::is_writable),
+        owner,
+        owned_by_current_user: owner.and_then(|owner| probe::current_uid().map(|uid| owner == uid)),
+        mode:                  metadata.as_ref().and_then(mode),
+        file_system:           probe_path.and_then(probe::file_system),
+        read_only_mount:       capacity.as_ref().map(|capacity| capacity.read_only),
+        available_space:       capacity.as_ref().map(|capacity| capacity.available)
+    }
+}
+
+#[cfg(unix)]
+fn owner(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.uid())
+}
+
+#[cfg(not(unix))]
+fn owner(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+fn ignored_vars() -> Vec<IgnoredVar> {
+    let mut ignored_vars = Vec::new();
+    for &name in XDG_VARIABLES.iter() {
+        let value = match env::var_os(name) {
+            Some(ref value) if value.is_empty() => continue,
+            Some(value)                         => value,
+            None                                => continue
+        };
+        let reason = if Platform::current() != Platform::Linux {
+            IgnoredVarReason::UnusedOnPlatform
+        } else if !Path::new(&value).is_absolute() {
+            IgnoredVarReason::RelativePath
+        } else {
+            continue;
+        };
+        ignored_vars.push(IgnoredVar { name, value, reason });
+    }
+    ignored_vars
+}
+
+#[cfg(test)]
+mod tests {
+    use test_util::TempDir;
+    use DirKind;
+    use DirWarning;
+    use Platform;
+
+    #[test]
+    fn test_diagnose() {
+        let temp_dir  = TempDir::new("doctor");
+        let base_dirs = Platform::Linux.base_dirs(&temp_dir);
+        let proj_dirs = Platform::Linux.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+
+        let diagnosis = proj_dirs.diagnose();
+        let cache     = diagnosis.dirs.iter().find(|dir| dir.kind == DirKind::Cache).unwrap();
+        assert_eq!(cache.path, temp_dir.join(".cache").join("barapp"));
+        assert!(!cache.exists && !cache.is_dir);
+        assert!(diagnosis.to_string().contains(&format!("Cache       {}\n", cache.path.display())));
+
+        #[cfg(unix)]
+        {
+            use std::fs;
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::create_dir_all(proj_dirs.cache_dir()).unwrap();
+            fs::set_permissions(proj_dirs.cache_dir(), fs::Permissions::from_mode(0o777)).unwrap();
+            let diagnosis = proj_dirs.diagnose();
+            let cache     = diagnosis.dirs.iter().find(|dir| dir.kind == DirKind::Cache).unwrap();
+            assert!(cache.exists && cache.is_dir && cache.writable);
+            assert_eq!(cache.mode, Some(0o777));
+            assert_eq!(cache.owned_by_current_user, Some(true));
+            assert!(cache.warnings().contains(&DirWarning::WorldWritable));
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::env;
+use std::error;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use export::is_variable_name;
+use passwd;
+use BaseDirs;
+use Platform;
+
+/// The reason why [`BaseDirs::expand`] or [`BaseDirs::expand_strict`] rejected a path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExpandError {
+    /// The user named by a `~user` prefix does not exist in the user database, which is only available on Unix.
+    UnknownUser(String),
+    /// The variable is neither one of the directories of the `BaseDirs` struct nor set in the environment.
+    /// Only returned by [`BaseDirs::expand_strict`].
+    UndefinedVariable(String),
+    /// A `${` is not followed by a variable name and a closing `}`.
+    MalformedVariable(String)
+}
+
+impl fmt::Display for ExpandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ExpandError::UnknownUser(ref name)       => write!(f, "unknown user `{}`", name),
+            ExpandError::UndefinedVariable(ref name) => write!(f, "undefined variable `{}`", name),
+            ExpandError::MalformedVariable(ref text) => write!(f, "malformed variable reference `{}`", text)
+        }
+    }
+}
+
+impl error::Error for ExpandError {}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Syntax {
+    Unix,
+    Windows
+}
+
+impl BaseDirs {
+    /// Expands a leading tilde and the variable references in `path`, using the directories of this struct
+    /// where the references name one of them, and the environment otherwise.
+    ///
+    /// On Linux and macOS:
+    ///
+    /// - `~` at the start of `path`, alone or followed by `/`, is replaced by the home directory.
+    /// - `~user` at the start of `path` is replaced by the home directory of `user` in the user database.
+    /// - `$VAR` and `${VAR}` are replaced by the value of the variable `VAR`, where `HOME`, `XDG_CACHE_HOME`,
+    ///   `XDG_CONFIG_HOME`, `XDG_DATA_HOME`, `XDG_STATE_HOME`, `XDG_BIN_HOME` and `XDG_RUNTIME_DIR` refer to the
+    ///   home, cache, config, data, state, executable and runtime directories of this struct.
+    ///   A `$` not followed by a variable name is kept.
+    ///
+    /// On Windows, following the rules of `ExpandEnvironmentStrings`:
+    ///
+    /// - `~` at the start of `path`, alone or followed by `\` or `/`, is replaced by the home directory.
+    /// - `%VAR%` is replaced by the value of the variable `VAR`, where variable names are case-insensitive and
+    ///   `USERPROFILE`, `APPDATA` and `LOCALAPPDATA` refer to the home, data and local data directories of this struct.
+    ///   A `%` without a closing `%` is kept.
+    ///
+    /// References to undefined variables are kept as they are; use [`BaseDirs::expand_strict`] to reject them instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::BaseDirs;
+    /// if let Some(base_dirs) = BaseDirs::new() {
+    ///     let notes = base_dirs.expand("~/notes").unwrap();
+    ///     assert_eq!(notes, base_dirs.home_dir().join("notes"));
+    /// }
+    /// ```
+    pub fn expand(&self, path: &str) -> Result<PathBuf, ExpandError> {
+        expand(self, path, current_syntax(), false)
+    }
+    /// Expands `path` like [`BaseDirs::expand`], but returns [`ExpandError::UndefinedVariable`]
+    /// if it refers to a variable which is not defined.
+    pub fn expand_strict(&self, path: &str) -> Result<PathBuf, ExpandError> {
+        expand(self, path, current_syntax(), true)
+    }
+}
+
+fn current_syntax() -> Syntax {
+    if Platform::current() == Platform::Windows { Syntax::Windows } else { Syntax::Unix }
+}
+
+fn expand(base_dirs: &BaseDirs, path: &str, syntax: Syntax, strict: bool) -> Result<PathBuf, ExpandError> {
+    let is_separator = |c: char| c == '/' || (syntax == Syntax::Windows && c == '\\');
+    let mut expanded = OsString::with_capacity(path.len());
+    let mut rest     = path;
+
+    if rest.starts_with('~') {
+        let end  = rest.find(is_separator).unwrap_or(rest.len());
+        let user = &rest[1..end];
+        if user.is_empty() {
+            expanded.push(base_dirs.home_dir());
+        } else if syntax == Syntax::Unix {
+            expanded.push(passwd::home_dir_of(user).ok_or_else(|| ExpandError::UnknownUser(user.to_string()))?);
+        } else {
+            expanded.push(&rest[..end]);
+        }
+        rest = &rest[end..];
+    }
+
+    match syntax {
+        Syntax::Unix => while let Some(index) = rest.find('$') {
+            expanded.push(&rest[..index]);
+            rest = &rest[index + 1..];
+            let (name, len) = if rest.starts_with('{') {
+                match rest.find('}') {
+                    Some(end) if is_variable_name(&rest[1..end]) => (&rest[1..end], end + 1),
+                    Some(end)                                    => return Err(ExpandError::MalformedVariable(format!("${}", &rest[..end + 1]))),
+                    None                                         => return Err(ExpandError::MalformedVariable(format!("${}", rest)))
+                }
+            } else {
+                let len = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+                if !is_variable_name(&rest[..len]) {
+                    expanded.push("$");
+                    continue;
+                }
+                (&rest[..len], len)
+            };
+            match lookup(base_dirs, name, syntax) {
+                Some(value)    => expanded.push(value),
+                None if strict => return Err(ExpandError::UndefinedVariable(name.to_string())),
+                None           => expanded.push(format!("${}", &rest[..len]))
+            }
+            rest = &rest[len..];
+        },
+        // An undefined variable is kept, and its closing `%` may start the next reference, as in `ExpandEnvironmentStrings`.
+        Syntax::Windows => while let Some(index) = rest.find('%') {
+            expanded.push(&rest[..index]);
+            rest = &rest[index + 1..];
+            let end = match rest.find('%') {
+                Some(end) => end,
+                None      => {
+                    expanded.push("%");
+                    break;
+                }
+            };
+            let name  = &rest[..end];
+            let value = if name.is_empty() { None } else { lookup(base_dirs, name, syntax) };
+            match value {
+                Some(value) => {
+                    expanded.push(value);
+                    rest = &rest[end + 1..];
+                },
+                None if strict && !name.is_empty() => return Err(ExpandError::UndefinedVariable(name.to_string())),
+                None => {
+                    expanded.push("%");
+                    expanded.push(name);
+                    rest = &rest[end..];
+                }
+            }
+        }
+    }
+    expanded.push(rest);
+    Ok(PathBuf::from(expanded))
+}
+
+fn lookup(base_dirs: &BaseDirs, name: &str, syntax: Syntax) -> Option<OsString> {
+    let dir: Option<&Path> = match syntax {
+        Syntax::Unix => match name {
+            "HOME"            => Some(base_dirs.home_dir()),
+            "XDG_CACHE_HOME"  => Some(base_dirs.cache_dir()),
+            "XDG_CONFIG_HOME" => Some(base_dirs.config_dir()),
+            "XDG_DATA_HOME"   => Some(base_dirs.data_dir()),
+            "XDG_STATE_HOME"  => base_dirs.state_dir(),
+            "XDG_BIN_HOME"    => base_dirs.executable_dir(),
+            "XDG_RUNTIME_DIR" => base_dirs.runtime_dir(),
+            _                 => None
+        },
+        Syntax::Windows => match name.to_ascii_uppercase().as_str() {
+            "USERPROFILE"     => Some(base_dirs.home_dir()),
+            "APPDATA"         => Some(base_dirs.data_dir()),
+            "LOCALAPPDATA"    => Some(base_dirs.data_local_dir()),
+            _                 => None
+        }
+    };
+    dir.map(|dir| dir.as_os_str().to_os_string()).or_else(|| env::var_os(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use super::expand;
+    use super::Syntax;
+    use ExpandError;
+    use Platform;
+
+    #[test]
+    fn test_expand_unix() {
+        let base_dirs = Platform::Linux.base_dirs(Path::new("/home/alice"));
+        let unix      = |path: &str, strict: bool| expand(&base_dirs, path, Syntax::Unix, strict);
+
+        assert_eq!(unix("~", false),                          Ok(PathBuf::from("/home/alice")));
+        assert_eq!(unix("~/notes", false),                    Ok(PathBuf::from("/home/alice/notes")));
+        assert_eq!(unix("$XDG_DATA_HOME/foo", false),         Ok(PathBuf::from("/home/alice/.local/share/foo")));
+        assert_eq!(unix("${HOME}s/$5 a$", false),             Ok(PathBuf::from("/home/alices/$5 a$")));
+        assert_eq!(unix("/x/$DIRECTORIES_UNDEFINED/y", false), Ok(PathBuf::from("/x/$DIRECTORIES_UNDEFINED/y")));
+        assert_eq!(unix("/x/$DIRECTORIES_UNDEFINED/y", true),  Err(ExpandError::UndefinedVariable("DIRECTORIES_UNDEFINED".to_string())));
+        assert_eq!(unix("${HOME", false),                     Err(ExpandError::MalformedVariable("${HOME".to_string())));
+        assert_eq!(unix("~directories-no-such-user/x", false), Err(ExpandError::UnknownUser("directories-no-such-user".to_string())));
+        assert_eq!(unix("a~/b", false),                       Ok(PathBuf::from("a~/b")));
+    }
+
+    #[test]
+    fn test_expand_windows() {
+        let base_dirs = Platform::Windows.base_dirs(Path::new("/Users/Alice"));
+        let windows   = |path: &str, strict: bool| expand(&base_dirs, path, Syntax::Windows, strict);
+
+        assert_eq!(windows("%AppData%\\foo", false),                  Ok(PathBuf::from("/Users/Alice/AppData/Roaming\\foo")));
+        assert_eq!(windows("%DIRECTORIES_UNDEFINED%%LOCALAPPDATA%", false), Ok(PathBuf::from("%DIRECTORIES_UNDEFINED%/Users/Alice/AppData/Local")));
+        assert_eq!(windows("%DIRECTORIES_UNDEFINED%", true),          Err(ExpandError::UndefinedVariable("DIRECTORIES_UNDEFINED".to_string())));
+        assert_eq!(windows("100% ~", false),                          Ok(PathBuf::from("100% ~")));
+        assert_eq!(windows("~\\x", false),                            Ok(PathBuf::from("/Users/Alice\\x")));
+    }
+}
@@ -0,0 +1,344 @@
+use std::error;
+use std::fmt;
+use std::path::Path;
+use std::str;
+
+use BaseDirs;
+use Platform;
+use ProjectDirs;
+
+/// `Shell` selects the syntax of the statements rendered by [`BaseDirs::export`] and [`ProjectDirs::export`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shell {
+    /// POSIX sh and compatible shells, e. g. `export XDG_CONFIG_HOME='/home/alice/.config'`.
+    Posix,
+    /// fish, e. g. `set -gx XDG_CONFIG_HOME '/home/alice/.config'`.
+    Fish,
+    /// PowerShell, e. g. `$env:XDG_CONFIG_HOME = '/home/alice/.config'`.
+    PowerShell,
+    /// Files read by systemd's `EnvironmentFile=`, e. g. `XDG_CONFIG_HOME="/home/alice/.config"`.
+    Systemd
+}
+
+/// The reason why [`BaseDirs::export`] or [`ProjectDirs::export`] could not render a path or a variable name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportError {
+    /// A path is not valid Unicode, which cannot be represented in PowerShell or systemd environment files,
+    /// or, on Windows, in any of the supported shells.
+    NonUnicodePath,
+    /// The prefix is not a valid variable name: it must be non-empty, start with an ASCII letter or underscore,
+    /// and consist only of ASCII letters, digits and underscores.
+    InvalidPrefix
+}
+
+impl Shell {
+    /// Quotes `path` as a single word in this shell, the same way as the values rendered by [`BaseDirs::export`],
+    /// e. g. for `KEY=value` lines which are `eval`'d or sourced by shell scripts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use directories::Shell;
+    ///
+    /// assert_eq!(Shell::Posix.quote(Path::new("/Users/Alice/Library/Application Support")).unwrap(), "'/Users/Alice/Library/Application Support'");
+    /// ```
+    pub fn quote(self, path: &Path) -> Result<String, ExportError> {
+        quote(self, path)
+    }
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            ExportError::NonUnicodePath => "path is not valid Unicode and cannot be represented in the selected shell",
+            ExportError::InvalidPrefix  => "prefix is not a valid variable name"
+        };
+        f.write_str(message)
+    }
+}
+
+impl error::Error for ExportError {}
+
+impl BaseDirs {
+    /// Renders the paths of this struct as statements which export them as environment variables in `shell`,
+    /// one statement per line.
+    ///
+    /// The variables are those [`Platform::current`] reads the directories from, see [`Platform::export`]:
+    /// on Linux, `HOME` and the XDG base directory variables, so that child processes using this library
+    /// resolve the same paths as the current process; on macOS and Windows, only `HOME`.
+    ///
+    /// Values are quoted so that the shell sees exactly the bytes of each path, including spaces, quotes and,
+    /// for [`Shell::Posix`] and [`Shell::Fish`] on Unix, bytes which are not valid UTF-8.
+    pub fn export(&self, shell: Shell) -> Result<String, ExportError> {
+        Platform::current().export(self, shell)
+    }
+}
+
+impl Platform {
+    /// Renders the paths of `base_dirs` as statements which export them as environment variables in `shell`,
+    /// one statement per line, quoted like in [`BaseDirs::export`].
+    ///
+    /// |Platform | Variables                                                                                                         |
+    /// | ------- | ----------------------------------------------------------------------------------------------------------------- |
+    /// | Linux   | `HOME`, `XDG_CACHE_HOME`, `XDG_CONFIG_HOME`, `XDG_DATA_HOME`, `XDG_STATE_HOME`, `XDG_BIN_HOME`, `XDG_RUNTIME_DIR` |
+    /// | macOS   | `HOME`                                                                                                            |
+    /// | Windows | `HOME`                                                                                                            |
+    ///
+    /// The XDG variables are only exported for Linux, where they determine the directories: on macOS and Windows,
+    /// they would make XDG-aware tools in child processes use e. g. `~/Library/Application Support` or `%APPDATA%`
+    /// instead of their own defaults. Unavailable directories are omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use directories::{Platform, Shell};
+    ///
+    /// let base_dirs = Platform::Linux.base_dirs(Path::new("/home/alice"));
+    /// let exports   = Platform::Linux.export(&base_dirs, Shell::Posix).unwrap();
+    /// assert!(exports.contains("export XDG_CONFIG_HOME='/home/alice/.config'\n"));
+    /// ```
+    pub fn export(self, base_dirs: &BaseDirs, shell: Shell) -> Result<String, ExportError> {
+        let mut variables = vec![("HOME", Some(base_dirs.home_dir()))];
+        if self == Platform::Linux {
+            variables.extend_from_slice(&[
+                ("XDG_CACHE_HOME",  Some(base_dirs.cache_dir())),
+                ("XDG_CONFIG_HOME", Some(base_dirs.config_dir())),
+                ("XDG_DATA_HOME",   Some(base_dirs.data_dir())),
+                ("XDG_STATE_HOME",  base_dirs.state_dir()),
+                ("XDG_BIN_HOME",    base_dirs.executable_dir()),
+                ("XDG_RUNTIME_DIR", base_dirs.runtime_dir())
+            ]);
+        }
+        render(shell, "", &variables)
+    }
+}
+
+impl ProjectDirs {
+    /// Renders the paths of this struct as statements which export them as environment variables in `shell`,
+    /// one statement per line.
+    ///
+    /// The variables are named after the getters, prefixed by the last component of the project path in uppercase,
+    /// without whitespace, e. g. `BARAPP_CACHE_DIR` for the project path `barapp`, `com.Foo-Corp.Bar-App` or `Foo Corp\Bar App`.
+    /// Other characters which are not ASCII letters or digits are replaced by `_`, and a prefix starting with a digit
+    /// is prefixed by `_`, e. g. `_3DSTUDIO`. If no prefix can be derived, `APP` is used.
+    /// Use [`ProjectDirs::export_with_prefix`] to choose the prefix.
+    ///
+    /// Values are quoted the same way as in [`BaseDirs::export`].
+    pub fn export(&self, shell: Shell) -> Result<String, ExportError> {
+        let application = self.project_path().file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        self.export_with_prefix(shell, &derive_prefix(application.rsplit('.').next().unwrap_or("")))
+    }
+    /// Renders the paths of this struct like [`ProjectDirs::export`], using `prefix` instead of the prefix
+    /// derived from the project path, e. g. `MY_APP` for `MY_APP_CACHE_DIR`.
+    pub fn export_with_prefix(&self, shell: Shell, prefix: &str) -> Result<String, ExportError> {
+        if !is_variable_name(prefix) {
+            return Err(ExportError::InvalidPrefix);
+        }
+        let variables = [
+            ("_CACHE_DIR",        Some(self.cache_dir())),
+            ("_CONFIG_DIR",       Some(self.config_dir())),
+            ("_CONFIG_LOCAL_DIR", Some(self.config_local_dir())),
+            ("_DATA_DIR",         Some(self.data_dir())),
+            ("_DATA_LOCAL_DIR",   Some(self.data_local_dir())),
+            ("_PREFERENCE_DIR",   Some(self.preference_dir())),
+            ("_RUNTIME_DIR",      self.runtime_dir()),
+            ("_STATE_DIR",        self.state_dir())
+        ];
+        render(shell, prefix, &variables)
+    }
+}
+
+// Derives a valid variable name from an application name.
+fn derive_prefix(application: &str) -> String {
+    let mut prefix: String = application.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    if prefix.chars().all(|c| c == '_') {
+        return "APP".to_string();
+    }
+    if prefix.starts_with(|c: char| c.is_ascii_digit()) {
+        prefix.insert(0, '_');
+    }
+    prefix
+}
+
+pub(crate) fn is_variable_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn render(shell: Shell, prefix: &str, variables: &[(&str, Option<&Path>)]) -> Result<String, ExportError> {
+    let mut output = String::new();
+    for &(name, path) in variables {
+        if let Some(path) = path {
+            let value = quote(shell, path)?;
+            let line  = match shell {
+                Shell::Posix      => format!("export {}{}={}\n", prefix, name, value),
+                Shell::Fish       => format!("set -gx {}{} {}\n", prefix, name, value),
+                Shell::PowerShell => format!("$env:{}{} = {}\n", prefix, name, value),
+                Shell::Systemd    => format!("{}{}={}\n", prefix, name, value)
+            };
+            output.push_str(&line);
+        }
+    }
+    Ok(output)
+}
+
+// A path is split into runs of valid UTF-8 and runs of bytes which are not; the latter are written as escapes,
+// so that the rendered statements are valid UTF-8 themselves.
+enum Segment<'a> {
+    Text(&'a str),
+    Bytes(&'a [u8])
+}
+
+#[cfg(unix)]
+fn segments<'a>(path: &'a Path) -> Result<Vec<Segment<'a>>, ExportError> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut segments = Vec::new();
+    let mut bytes    = path.as_os_str().as_bytes();
+    while !bytes.is_empty() {
+        match str::from_utf8(bytes) {
+            Ok(text) => {
+                segments.push(Segment::Text(text));
+                break;
+            },
+            Err(error) => {
+                let (valid, rest) = bytes.split_at(error.valid_up_to());
+                let (invalid, rest) = rest.split_at(error.error_len().unwrap_or(rest.len()));
+                if !valid.is_empty() {
+                    segments.push(Segment::Text(str::from_utf8(valid).unwrap()));
+                }
+                segments.push(Segment::Bytes(invalid));
+                bytes = rest;
+            }
+        }
+    }
+    Ok(segments)
+}
+
+#[cfg(not(unix))]
+fn segments<'a>(path: &'a Path) -> Result<Vec<Segment<'a>>, ExportError> {
+    path.to_str().map(|text| vec![Segment::Text(text)]).ok_or(ExportError::NonUnicodePath)
+}
+
+fn quote(shell: Shell, path: &Path) -> Result<String, ExportError> {
+    let mut quoted = String::new();
+    for segment in segments(path)? {
+        match (shell, segment) {
+            // Single quotes preserve every character except the single quote itself.
+            (Shell::Posix, Segment::Text(text)) => {
+                quoted.push('\'');
+                quoted.push_str(&text.replace('\'', "'\\''"));
+                quoted.push('\'');
+            },
+            // The command substitution only ever prints bytes which are not valid UTF-8, so no trailing newlines are stripped.
+            (Shell::Posix, Segment::Bytes(bytes)) => {
+                quoted.push_str("\"$(printf '");
+                for byte in bytes {
+                    quoted.push_str(&format!("\\{:03o}", byte));
+                }
+                quoted.push_str("')\"");
+            },
+            (Shell::Fish, Segment::Text(text)) => {
+                quoted.push('\'');
+                quoted.push_str(&text.replace('\\', "\\\\").replace('\'', "\\'"));
+                quoted.push('\'');
+            },
+            (Shell::Fish, Segment::Bytes(bytes)) => {
+                for byte in bytes {
+                    quoted.push_str(&format!("\\X{:02X}", byte));
+                }
+            },
+            // PowerShell also treats the typographic single quotes as quotes, which are escaped by doubling them.
+            (Shell::PowerShell, Segment::Text(text)) => {
+                quoted.push('\'');
+                for c in text.chars() {
+                    if let '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' = c {
+                        quoted.push(c);
+                    }
+                    quoted.push(c);
+                }
+                quoted.push('\'');
+            },
+            (Shell::Systemd, Segment::Text(text)) => {
+                quoted.push('"');
+                for c in text.chars() {
+                    if let '\\' | '"' | '`' | '$' = c {
+                        quoted.push('\\');
+                    }
+                    quoted.push(c);
+                }
+                quoted.push('"');
+            },
+            (Shell::PowerShell, Segment::Bytes(_)) | (Shell::Systemd, Segment::Bytes(_)) => return Err(ExportError::NonUnicodePath)
+        }
+    }
+    Ok(quoted)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use ExportError;
+    use Platform;
+    use Shell;
+
+    #[test]
+    fn test_export() {
+        let base_dirs = Platform::Linux.base_dirs(Path::new("/home/alice's files"));
+        let exports   = Platform::Linux.export(&base_dirs, Shell::Posix).unwrap();
+        assert!(exports.starts_with("export HOME='/home/alice'\\''s files'\n"), "{}", exports);
+        assert!(exports.contains("export XDG_CONFIG_HOME='/home/alice'\\''s files/.config'\n"), "{}", exports);
+        assert!(!exports.contains("XDG_RUNTIME_DIR"));
+        let exports   = Platform::Linux.export(&base_dirs, Shell::Fish).unwrap();
+        assert!(exports.starts_with("set -gx HOME '/home/alice\\'s files'\n"), "{}", exports);
+        let exports   = Platform::Linux.export(&base_dirs, Shell::PowerShell).unwrap();
+        assert!(exports.starts_with("$env:HOME = '/home/alice''s files'\n"), "{}", exports);
+        assert_eq!(base_dirs.export(Shell::Posix), Platform::current().export(&base_dirs, Shell::Posix));
+
+        // The XDG variables would redirect XDG-aware tools to the directories of macOS or Windows.
+        let base_dirs = Platform::Windows.base_dirs(Path::new("/Users/Alice"));
+        assert_eq!(Platform::Windows.export(&base_dirs, Shell::Posix).unwrap(), "export HOME='/Users/Alice'\n");
+        let base_dirs = Platform::MacOs.base_dirs(Path::new("/Users/Alice"));
+        assert_eq!(Platform::MacOs.export(&base_dirs, Shell::Posix).unwrap(), "export HOME='/Users/Alice'\n");
+
+        let proj_dirs = Platform::MacOs.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        let exports   = proj_dirs.export(Shell::Systemd).unwrap();
+        assert!(exports.starts_with("BARAPP_CACHE_DIR=\"/Users/Alice/Library/Caches/com.Foo-Corp.Bar-App\"\n"), "{}", exports);
+        assert!(proj_dirs.export_with_prefix(Shell::Systemd, "MY_APP").unwrap().starts_with("MY_APP_CACHE_DIR="));
+        assert_eq!(proj_dirs.export_with_prefix(Shell::Systemd, "1APP"), Err(ExportError::InvalidPrefix));
+    }
+
+    #[test]
+    fn test_derive_prefix() {
+        use super::derive_prefix;
+
+        assert_eq!(derive_prefix("Bar-App"),   "BARAPP");
+        assert_eq!(derive_prefix("3D Studio"), "_3DSTUDIO");
+        assert_eq!(derive_prefix("café"),      "CAF_");
+        assert_eq!(derive_prefix("日本語"),    "APP");
+        assert_eq!(derive_prefix(""),          "APP");
+
+        let base_dirs = Platform::Windows.base_dirs(Path::new("/Users/Alice"));
+        for application in &["3D Studio", "日本語"] {
+            let proj_dirs = Platform::Windows.project(&base_dirs, "", "Foo Corp", application).unwrap();
+            assert!(proj_dirs.export(Shell::Posix).is_ok());
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_export_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let base_dirs = Platform::Linux.base_dirs(Path::new(OsStr::from_bytes(b"/home/al\xFFice")));
+        assert!(Platform::Linux.export(&base_dirs, Shell::Posix).unwrap().starts_with("export HOME='/home/al'\"$(printf '\\377')\"'ice'\n"));
+        assert!(Platform::Linux.export(&base_dirs, Shell::Fish).unwrap().starts_with("set -gx HOME '/home/al'\\XFF'ice'\n"));
+        assert_eq!(Platform::Linux.export(&base_dirs, Shell::Systemd), Err(ExportError::NonUnicodePath));
+    }
+}
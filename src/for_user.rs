@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use passwd;
+use BaseDirs;
+use Platform;
+use UserDirs;
+
+impl BaseDirs {
+    /// Creates a `BaseDirs` struct for another user, identified by user name or, if no user has that name, by numeric user id,
+    /// e. g. for administrative tools running as root.
+    ///
+    /// The home directory is taken from the user database. The other user's environment, and therefore the values of
+    /// `$XDG_CACHE_HOME`, `$XDG_CONFIG_HOME` etc. in their sessions, cannot be known: the returned paths are the
+    /// **defaults assumed** relative to the home directory, as returned by [`Platform::base_dirs`],
+    /// and may differ from the paths the user's own programs use. The runtime directory is always `None`.
+    ///
+    /// Returns `None` if the user does not exist or has no home directory, and always on Windows,
+    /// where no user database is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::BaseDirs;
+    /// if let Some(base_dirs) = BaseDirs::for_user("alice") {
+    ///     base_dirs.config_dir();
+    ///     // Linux: /home/alice/.config, even if alice sets $XDG_CONFIG_HOME
+    ///     // macOS: /Users/alice/Library/Application Support
+    /// }
+    /// ```
+    pub fn for_user(user: &str) -> Option<BaseDirs> {
+        home_dir_of_user(user).map(|home_dir| Platform::current().base_dirs(&home_dir))
+    }
+}
+
+impl UserDirs {
+    /// Creates a `UserDirs` struct for another user, identified by user name or, if no user has that name, by numeric user id.
+    ///
+    /// The home directory is taken from the user database. On Linux, the user directories are read from
+    /// `~/.config/user-dirs.dirs` in that home directory, and the font directory is `~/.local/share/fonts`:
+    /// like in [`BaseDirs::for_user`], the other user's `$XDG_CONFIG_HOME` and `$XDG_DATA_HOME` cannot be known,
+    /// so their **defaults are assumed**. On macOS, the defaults returned by [`Platform::user_dirs`] are used.
+    ///
+    /// Returns `None` if the user does not exist or has no home directory, and always on Windows,
+    /// where no user database is available.
+    pub fn for_user(user: &str) -> Option<UserDirs> {
+        home_dir_of_user(user).map(user_dirs_in)
+    }
+}
+
+// Returns the user directories of the user with the home directory `home_dir`, assuming the default locations.
+pub(crate) fn user_dirs_in(home_dir: PathBuf) -> UserDirs {
+    let platform = Platform::current();
+    if platform != Platform::Linux {
+        return platform.user_dirs(&home_dir);
+    }
+
+    let bytes   = fs::read(home_dir.join(".config").join("user-dirs.dirs")).unwrap_or_default();
+    let entries = parse_user_dirs(&home_dir, &bytes);
+    let entry   = |key: &str| entries.iter().rev().find(|&(name, _)| name == key).map(|(_, path)| Arc::from(path.as_path()));
+    UserDirs {
+        home_dir:     home_dir.as_path().into(),
+        audio_dir:    entry("MUSIC"),
+        desktop_dir:  entry("DESKTOP"),
+        document_dir: entry("DOCUMENTS"),
+        download_dir: entry("DOWNLOAD"),
+        font_dir:     Some(home_dir.join(".local").join("share").join("fonts").into()),
+        picture_dir:  entry("PICTURES"),
+        public_dir:   entry("PUBLICSHARE"),
+        template_dir: entry("TEMPLATES"),
+        video_dir:    entry("VIDEOS")
+    }
+}
+
+fn home_dir_of_user(user: &str) -> Option<PathBuf> {
+    passwd::home_dir_of(user).or_else(|| user.parse().ok().and_then(passwd::home_dir_of_uid))
+}
+
+// Parses the `XDG_<NAME>_DIR="<path>"` lines of a user-dirs.dirs file the same way as `dirs_sys::user_dirs`,
+// which cannot be used here as it consults `$XDG_CONFIG_HOME` of the current process to locate the file.
+// Entries are returned in the order of the file; like in `dirs_sys::user_dirs`, the last one of duplicate keys takes effect.
+fn parse_user_dirs(home_dir: &Path, bytes: &[u8]) -> Vec<(String, PathBuf)> {
+    let mut entries = Vec::new();
+    for line in bytes.split(|&b| b == b'\n') {
+        let index = match line.iter().position(|&b| b == b'=') {
+            Some(index) => index,
+            None        => continue
+        };
+        let key   = trim_blank(&line[..index]);
+        let value = trim_blank(&line[index + 1..]);
+        if !key.starts_with(b"XDG_") || !key.ends_with(b"_DIR") || key.len() < 8 || value.len() < 2 || !value.starts_with(b"\"") || !value.ends_with(b"\"") {
+            continue;
+        }
+        let name  = match String::from_utf8(key[4..key.len() - 4].to_vec()) {
+            Ok(name) => name,
+            Err(_)   => continue
+        };
+        let value = &value[1..value.len() - 1];
+        // A directory pointing to the home directory itself is disabled.
+        let path  = if value == b"$HOME/" {
+            continue;
+        } else if value.starts_with(b"$HOME/") {
+            home_dir.join(path_from_bytes(unescape(&value[6..])))
+        } else if value.starts_with(b"/") {
+            path_from_bytes(unescape(value))
+        } else {
+            continue;
+        };
+        entries.push((name, path));
+    }
+    entries
+}
+
+fn trim_blank(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().take_while(|&&b| b == b' ' || b == b'\t').count();
+    let end   = bytes.len() - bytes[start..].iter().rev().take_while(|&&b| b == b' ' || b == b'\t').count();
+    &bytes[start..end]
+}
+
+// Removes the backslashes which escape characters inside double quotes in POSIX shells.
+fn unescape(bytes: &[u8]) -> Vec<u8> {
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut iter      = bytes.iter().cloned().peekable();
+    while let Some(b) = iter.next() {
+        match iter.peek() {
+            Some(&next) if b == b'\\' && (next == b'$' || next == b'`' || next == b'"' || next == b'\\') => {
+                unescaped.push(next);
+                iter.next();
+            },
+            _ => unescaped.push(b)
+        }
+    }
+    unescaped
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: Vec<u8>) -> PathBuf {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    PathBuf::from(OsString::from_vec(bytes))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: Vec<u8>) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use super::parse_user_dirs;
+
+    #[test]
+    fn test_parse_user_dirs() {
+        let file = b"# comment\nXDG_DESKTOP_DIR=\"$HOME/Desktop\"\n  XDG_MUSIC_DIR = \"/srv/music \\\"x\\\"\"\nXDG_VIDEOS_DIR=\"$HOME/\"\nXDG_PUBLICSHARE_DIR=Public\n";
+        let entries = parse_user_dirs(Path::new("/home/alice"), file);
+        assert_eq!(entries, vec![
+            ("DESKTOP".to_string(), PathBuf::from("/home/alice/Desktop")),
+            ("MUSIC".to_string(),   PathBuf::from("/srv/music \"x\""))
+        ]);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_duplicate_keys() {
+        use std::fs;
+
+        use test_util::TempDir;
+
+        let home_dir = TempDir::new("for-user");
+        fs::create_dir_all(home_dir.join(".config")).unwrap();
+        fs::write(home_dir.join(".config").join("user-dirs.dirs"), "XDG_MUSIC_DIR=\"$HOME/Music\"\nXDG_MUSIC_DIR=\"$HOME/Audio\"\n").unwrap();
+        assert_eq!(super::user_dirs_in(home_dir.to_path_buf()).audio_dir(), Some(home_dir.join("Audio").as_path()));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_for_user() {
+        assert_eq!(::BaseDirs::for_user("directories-no-such-user"), None);
+        if let Some(base_dirs) = ::BaseDirs::for_user("root") {
+            assert_eq!(Some(base_dirs), ::BaseDirs::for_user("0"));
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use BaseDirs;
+use ProjectDirs;
+use ProjectDirsError;
+use UserDirs;
+
+static GLOBAL: RwLock<Option<Arc<Snapshot>>> = RwLock::new(None);
+
+/// `Snapshot` holds a `BaseDirs` and a `UserDirs` struct computed at the same point in time.
+///
+/// The process-wide snapshot is returned by [`global`] and replaced by [`refresh`].
+///
+/// # Examples
+///
+/// ```
+/// if let Some(base_dirs) = directories::global().base_dirs() {
+///     base_dirs.config_dir();
+///     // Linux:   /home/alice/.config
+///     // Windows: C:\Users\Alice\AppData\Roaming
+///     // macOS:   /Users/Alice/Library/Application Support
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    base_dirs: Option<BaseDirs>,
+    user_dirs: Option<UserDirs>
+}
+
+impl Snapshot {
+    fn new() -> Snapshot {
+        Snapshot {
+            base_dirs: BaseDirs::new(),
+            user_dirs: UserDirs::new()
+        }
+    }
+    /// Returns the `BaseDirs` struct of this snapshot, or `None` if no valid home directory path could be retrieved from the operating system.
+    pub fn base_dirs(&self) -> Option<&BaseDirs> {
+        self.base_dirs.as_ref()
+    }
+    /// Returns the `UserDirs` struct of this snapshot, or `None` if no valid home directory path could be retrieved from the operating system.
+    pub fn user_dirs(&self) -> Option<&UserDirs> {
+        self.user_dirs.as_ref()
+    }
+    /// Creates a `ProjectDirs` struct from values describing the project, deriving its paths from the `BaseDirs` struct of this snapshot.
+    ///
+    /// See [`BaseDirs::project`] for details; [`ProjectDirsError::NoHomeDir`] is returned if this snapshot has no `BaseDirs` struct.
+    pub fn project(&self, qualifier: &str, organization: &str, application: &str) -> Result<ProjectDirs, ProjectDirsError> {
+        match self.base_dirs {
+            Some(ref base_dirs) => base_dirs.project(qualifier, organization, application),
+            None                => Err(ProjectDirsError::NoHomeDir)
+        }
+    }
+}
+
+/// Returns the process-wide snapshot of the system's paths.
+///
+/// The snapshot is computed when `global()` is invoked for the first time, and is shared by all threads afterwards.
+/// Later changes to the environment are not reflected until [`refresh`] is invoked.
+///
+/// Cloning the returned `Arc`, or the `BaseDirs` and `UserDirs` structs it holds, does not allocate:
+/// all structs of this library share their paths by reference counting.
+pub fn global() -> Arc<Snapshot> {
+    if let Some(ref snapshot) = *GLOBAL.read().unwrap_or_else(PoisonError::into_inner) {
+        return snapshot.clone();
+    }
+    let mut global = GLOBAL.write().unwrap_or_else(PoisonError::into_inner);
+    global.get_or_insert_with(|| Arc::new(Snapshot::new())).clone()
+}
+
+/// Replaces the process-wide snapshot returned by [`global`] with a new snapshot of the system's paths, and returns it.
+///
+/// Snapshots previously returned by `global()` are not modified.
+pub fn refresh() -> Arc<Snapshot> {
+    let snapshot = Arc::new(Snapshot::new());
+    *GLOBAL.write().unwrap_or_else(PoisonError::into_inner) = Some(snapshot.clone());
+    snapshot
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    #[test]
+    fn test_global_and_refresh() {
+        let snapshot = ::global();
+        assert!(Arc::ptr_eq(&snapshot, &::global()));
+        assert_eq!(snapshot.base_dirs().is_some(), ::BaseDirs::new().is_some());
+
+        let refreshed = ::refresh();
+        assert!(!Arc::ptr_eq(&snapshot, &refreshed));
+        assert!(Arc::ptr_eq(&refreshed, &::global()));
+    }
+}
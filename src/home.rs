@@ -0,0 +1,432 @@
+#[cfg(unix)]
+extern crate libc;
+
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use for_user::user_dirs_in;
+use passwd;
+use probe;
+use BaseDirs;
+use Platform;
+use UserDirs;
+
+/// `HomePolicy` selects whose home directory is used when the process runs with the privileges of another user,
+/// e. g. under `sudo`, where `$HOME` may be the home directory of either the invoking or the target user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HomePolicy {
+    /// Use the home directory of the effective user, e. g. `/root` under `sudo`, so that created files belong to their owner.
+    EffectiveUser,
+    /// Use the home directory of the user who invoked `sudo`, `pkexec` or a setuid program.
+    InvokingUser,
+    /// Return [`HomeError::Ambiguous`] if the process runs with the privileges of another user,
+    /// or `$HOME` is not the home directory of the effective user.
+    Error
+}
+
+/// The way in which the process obtained the privileges of another user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Elevation {
+    /// The process was started by `sudo`, which sets `$SUDO_UID` and `$SUDO_USER`.
+    Sudo,
+    /// The process was started by `pkexec`, which sets `$PKEXEC_UID`.
+    Pkexec,
+    /// The real user id of the process differs from its effective user id, e. g. in a setuid program.
+    Setuid
+}
+
+/// `HomeSituation` describes the users and home directories relevant for the process, as detected by [`HomeSituation::detect`].
+///
+/// All fields are `None` on platforms without a user database, i. e. Windows.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HomeSituation {
+    /// The way in which the process obtained the privileges of another user, if any.
+    pub elevation:      Option<Elevation>,
+    /// The effective user id of the process.
+    pub effective_uid:  Option<u32>,
+    /// The home directory of the effective user in the user database.
+    pub effective_home: Option<PathBuf>,
+    /// The id of the user who invoked `sudo`, `pkexec` or the setuid program.
+    pub invoking_uid:   Option<u32>,
+    /// The home directory of the invoking user in the user database.
+    pub invoking_home:  Option<PathBuf>,
+    /// The value of `$HOME`, if it is set and not empty.
+    pub home_var:       Option<PathBuf>
+}
+
+/// `HomeFallback` selects what [`BaseDirs::with_home_fallback`] does if the home directory is not usable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HomeFallback {
+    /// Return [`HomeError::Unusable`].
+    Error,
+    /// Use a new, private directory in the temporary directory of the system as home directory,
+    /// which is removed with its contents when the returned [`FallbackDirs`] is dropped.
+    TempRoot,
+    /// Use the given directory as home directory, which must be an absolute path.
+    Root(PathBuf)
+}
+
+/// The reason why a home directory is not usable, as detected by [`HomeProblem::check`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HomeProblem {
+    /// No home directory could be retrieved from the operating system, e. g. because `$HOME` is unset
+    /// and the user has no entry in the user database.
+    Unknown,
+    /// The home directory is the root directory, e. g. `/` in containers where `$HOME` was never set.
+    RootDir,
+    /// The home directory does not exist.
+    Nonexistent,
+    /// The home directory is not a directory.
+    NotADirectory,
+    /// The home directory cannot be written by the current user.
+    NotWritable
+}
+
+/// `FallbackDirs` holds the `BaseDirs` struct returned by [`BaseDirs::with_home_fallback`],
+/// and the problem with the home directory which caused the fallback to be used.
+///
+/// If [`HomeFallback::TempRoot`] was used, the temporary home directory is removed when this struct is dropped,
+/// even if clones of the `BaseDirs` struct still exist.
+#[derive(Debug)]
+pub struct FallbackDirs {
+    base_dirs: BaseDirs,
+    problem:   Option<HomeProblem>,
+    temp_root: Option<PathBuf>
+}
+
+/// The reason why no home directory could be chosen for a [`HomePolicy`] or [`HomeFallback`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HomeError {
+    /// The home directory is ambiguous and [`HomePolicy::Error`] was requested.
+    Ambiguous(HomeSituation),
+    /// The home directory of the user selected by the policy could not be determined.
+    NotFound,
+    /// The home directory is not usable and [`HomeFallback::Error`] was requested,
+    /// or the temporary home directory of [`HomeFallback::TempRoot`] could not be created.
+    Unusable(HomeProblem),
+    /// The directory of [`HomeFallback::Root`] is not an absolute path, which would result in relative base directories.
+    RelativeRoot
+}
+
+impl HomeSituation {
+    /// Detects whether the process runs with the privileges of another user, and the home directories of the users involved.
+    ///
+    /// `$SUDO_UID` (or `$SUDO_USER`) and `$PKEXEC_UID` are only taken into account if they name a user
+    /// other than the effective user.
+    pub fn detect() -> HomeSituation {
+        let effective_uid  = probe::current_uid();
+        let effective_home = effective_uid.and_then(passwd::home_dir_of_uid);
+        let home_var       = env::var_os("HOME").filter(|home| !home.is_empty()).map(PathBuf::from);
+        let (elevation, invoking_uid, invoking_home) = match effective_uid {
+            Some(effective_uid) => invoking_user(effective_uid, effective_home.as_ref()),
+            None                => (None, None, None)
+        };
+        HomeSituation {
+            elevation,
+            effective_uid,
+            effective_home,
+            invoking_uid,
+            invoking_home,
+            home_var
+        }
+    }
+    /// Returns whether `$HOME` is set to a directory other than the home directory of the effective user.
+    pub fn home_mismatch(&self) -> bool {
+        match (&self.home_var, &self.effective_home) {
+            (Some(home_var), Some(effective_home)) => home_var != effective_home,
+            _                                      => false
+        }
+    }
+    /// Returns whether the home directory depends on the [`HomePolicy`],
+    /// i. e. the process runs with the privileges of another user or `$HOME` does not belong to the effective user.
+    pub fn is_ambiguous(&self) -> bool {
+        self.elevation.is_some() || self.home_mismatch()
+    }
+}
+
+fn invoking_user(effective_uid: u32, effective_home: Option<&PathBuf>) -> (Option<Elevation>, Option<u32>, Option<PathBuf>) {
+    let var_uid = |name: &str| env::var(name).ok().and_then(|uid| uid.parse::<u32>().ok()).filter(|&uid| uid != effective_uid);
+
+    if let Some(uid) = var_uid("SUDO_UID") {
+        return (Some(Elevation::Sudo), Some(uid), passwd::home_dir_of_uid(uid));
+    }
+    // `$SUDO_USER` is only consulted if `$SUDO_UID` is not set, e. g. because of an `env_delete` rule in the sudoers file.
+    if env::var_os("SUDO_UID").is_none() {
+        if let Some(home) = env::var("SUDO_USER").ok().and_then(|name| passwd::home_dir_of(&name)).filter(|home| Some(home) != effective_home) {
+            return (Some(Elevation::Sudo), None, Some(home));
+        }
+    }
+    if let Some(uid) = var_uid("PKEXEC_UID") {
+        return (Some(Elevation::Pkexec), Some(uid), passwd::home_dir_of_uid(uid));
+    }
+    match real_uid() {
+        Some(uid) if uid != effective_uid => (Some(Elevation::Setuid), Some(uid), passwd::home_dir_of_uid(uid)),
+        _                                 => (None, None, None)
+    }
+}
+
+#[cfg(unix)]
+fn real_uid() -> Option<u32> {
+    Some(unsafe { libc::getuid() })
+}
+
+#[cfg(not(unix))]
+fn real_uid() -> Option<u32> {
+    None
+}
+
+// Returns the home directory selected by `policy`, or `None` if the default lookup of `BaseDirs::new` applies.
+fn select_home(situation: &HomeSituation, policy: HomePolicy) -> Result<Option<PathBuf>, HomeError> {
+    if !situation.is_ambiguous() {
+        return Ok(None);
+    }
+    match policy {
+        HomePolicy::EffectiveUser                                 => situation.effective_home.clone().map(Some).ok_or(HomeError::NotFound),
+        HomePolicy::InvokingUser if situation.elevation.is_some() => situation.invoking_home.clone().map(Some).ok_or(HomeError::NotFound),
+        // Without elevation, e. g. under `su -m`, `$HOME` is the only hint of the invoking user.
+        HomePolicy::InvokingUser                                  => Ok(None),
+        HomePolicy::Error                                         => Err(HomeError::Ambiguous(situation.clone()))
+    }
+}
+
+impl BaseDirs {
+    /// Creates a `BaseDirs` struct like [`BaseDirs::new`], choosing the home directory according to `policy`
+    /// if the process runs with the privileges of another user, or `$HOME` is not the home directory of the effective user.
+    ///
+    /// In these cases, the `XDG_*` variables may belong to either user, so they are ignored and the default locations
+    /// relative to the selected home directory are used, like in [`BaseDirs::for_user`].
+    /// Otherwise, the result is the same as that of [`BaseDirs::new`]. Use [`HomeSituation::detect`] to report the situation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::{BaseDirs, HomePolicy};
+    /// // Under `sudo`, keep writing to the cache directory of the invoking user.
+    /// let base_dirs = BaseDirs::with_home_policy(HomePolicy::InvokingUser);
+    /// ```
+    pub fn with_home_policy(policy: HomePolicy) -> Result<BaseDirs, HomeError> {
+        match select_home(&HomeSituation::detect(), policy)? {
+            Some(home_dir) => Ok(Platform::current().base_dirs(&home_dir)),
+            None           => BaseDirs::new().ok_or(HomeError::NotFound)
+        }
+    }
+}
+
+impl UserDirs {
+    /// Creates a `UserDirs` struct like [`UserDirs::new`], choosing the home directory according to `policy`.
+    ///
+    /// See [`BaseDirs::with_home_policy`] for details.
+    pub fn with_home_policy(policy: HomePolicy) -> Result<UserDirs, HomeError> {
+        match select_home(&HomeSituation::detect(), policy)? {
+            Some(home_dir) => Ok(user_dirs_in(home_dir)),
+            None           => UserDirs::new().ok_or(HomeError::NotFound)
+        }
+    }
+}
+
+impl HomeProblem {
+    /// Checks whether `home_dir` can be used as home directory, returning the problem if it cannot.
+    pub fn check(home_dir: &Path) -> Option<HomeProblem> {
+        if home_dir.components().all(|component| matches!(component, Component::Prefix(_) | Component::RootDir)) {
+            return Some(HomeProblem::RootDir);
+        }
+        match fs::metadata(home_dir) {
+            Err(_)                                 => Some(HomeProblem::Nonexistent),
+            Ok(ref metadata) if !metadata.is_dir() => Some(HomeProblem::NotADirectory),
+            Ok(_) if !probe::is_writable(home_dir) => Some(HomeProblem::NotWritable),
+            Ok(_)                                  => None
+        }
+    }
+}
+
+impl FallbackDirs {
+    /// Returns the `BaseDirs` struct, which is relative to the fallback root if [`FallbackDirs::problem`] returns a problem.
+    pub fn base_dirs(&self) -> &BaseDirs {
+        &self.base_dirs
+    }
+    /// Returns the problem with the home directory of the user, or `None` if it is usable and no fallback was used.
+    pub fn problem(&self) -> Option<HomeProblem> {
+        self.problem
+    }
+    /// Returns whether the home directory is a temporary directory, which is removed when this struct is dropped.
+    pub fn is_temporary(&self) -> bool {
+        self.temp_root.is_some()
+    }
+}
+
+impl Drop for FallbackDirs {
+    fn drop(&mut self) {
+        if let Some(ref temp_root) = self.temp_root {
+            let _ = fs::remove_dir_all(temp_root);
+        }
+    }
+}
+
+impl BaseDirs {
+    /// Creates a `BaseDirs` struct like [`BaseDirs::new`] if the home directory is usable, and according to `fallback` otherwise.
+    ///
+    /// The home directory is not usable if it cannot be retrieved from the operating system, is the root directory,
+    /// does not exist, or cannot be written, as checked by [`HomeProblem::check`].
+    /// With a fallback root, the default locations relative to that root are used, ignoring the `XDG_*` variables.
+    /// A [`HomeFallback::Root`] which is not an absolute path is rejected with [`HomeError::RelativeRoot`],
+    /// whether or not the home directory is usable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::{BaseDirs, HomeFallback};
+    /// // In a minimal container, keep caches in a temporary directory for the lifetime of `dirs`.
+    /// if let Ok(dirs) = BaseDirs::with_home_fallback(HomeFallback::TempRoot) {
+    ///     if let Some(problem) = dirs.problem() {
+    ///         eprintln!("warning: home directory not usable ({}), data will not persist", problem);
+    ///     }
+    ///     dirs.base_dirs().cache_dir();
+    /// }
+    /// ```
+    pub fn with_home_fallback(fallback: HomeFallback) -> Result<FallbackDirs, HomeError> {
+        if let HomeFallback::Root(ref root) = fallback {
+            if !root.is_absolute() {
+                return Err(HomeError::RelativeRoot);
+            }
+        }
+        let problem = match BaseDirs::new() {
+            Some(base_dirs) => match HomeProblem::check(base_dirs.home_dir()) {
+                Some(problem) => problem,
+                None          => return Ok(FallbackDirs { base_dirs, problem: None, temp_root: None })
+            },
+            None            => HomeProblem::Unknown
+        };
+        match fallback {
+            HomeFallback::Error      => Err(HomeError::Unusable(problem)),
+            HomeFallback::TempRoot   => {
+                let temp_root = create_temp_root().map_err(|_| HomeError::Unusable(problem))?;
+                Ok(FallbackDirs { base_dirs: Platform::current().base_dirs(&temp_root), problem: Some(problem), temp_root: Some(temp_root) })
+            },
+            HomeFallback::Root(root) => Ok(FallbackDirs { base_dirs: Platform::current().base_dirs(&root), problem: Some(problem), temp_root: None })
+        }
+    }
+}
+
+// Creates a directory only accessible by the current user, with a name unique to this process and call.
+fn create_temp_root() -> io::Result<PathBuf> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let path = env::temp_dir().join(format!("directories-home-{}-{}", process::id(), COUNTER.fetch_add(1, Ordering::Relaxed)));
+    probe::create_private_dir(&path)?;
+    Ok(path)
+}
+
+impl fmt::Display for Elevation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Elevation::Sudo   => "sudo",
+            Elevation::Pkexec => "pkexec",
+            Elevation::Setuid => "setuid"
+        };
+        f.write_str(name)
+    }
+}
+
+impl fmt::Display for HomeProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            HomeProblem::Unknown       => "home directory could not be determined",
+            HomeProblem::RootDir       => "home directory is the root directory",
+            HomeProblem::Nonexistent   => "home directory does not exist",
+            HomeProblem::NotADirectory => "home directory is not a directory",
+            HomeProblem::NotWritable   => "home directory is not writable"
+        };
+        f.write_str(message)
+    }
+}
+
+impl fmt::Display for HomeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HomeError::Ambiguous(ref situation) => {
+                f.write_str("home directory is ambiguous")?;
+                if let Some(elevation) = situation.elevation {
+                    write!(f, ": running under {}", elevation)?;
+                }
+                if let (true, Some(home_var), Some(effective_home)) = (situation.home_mismatch(), &situation.home_var, &situation.effective_home) {
+                    write!(f, ", $HOME is {} but the home directory of the effective user is {}", home_var.display(), effective_home.display())?;
+                }
+                Ok(())
+            },
+            HomeError::NotFound          => f.write_str("home directory could not be determined"),
+            HomeError::Unusable(problem) => write!(f, "home directory is not usable: {}", problem),
+            HomeError::RelativeRoot      => f.write_str("fallback home directory is not an absolute path")
+        }
+    }
+}
+
+impl error::Error for HomeError {}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::select_home;
+    use BaseDirs;
+    use Elevation;
+    use HomeError;
+    use HomeFallback;
+    use HomePolicy;
+    use HomeProblem;
+    use HomeSituation;
+
+    #[test]
+    fn test_select_home() {
+        let mut situation = HomeSituation {
+            elevation:      Some(Elevation::Sudo),
+            effective_uid:  Some(0),
+            effective_home: Some(PathBuf::from("/root")),
+            invoking_uid:   Some(1000),
+            invoking_home:  Some(PathBuf::from("/home/alice")),
+            home_var:       Some(PathBuf::from("/home/alice"))
+        };
+        assert!(situation.home_mismatch());
+        assert_eq!(select_home(&situation, HomePolicy::EffectiveUser), Ok(Some(PathBuf::from("/root"))));
+        assert_eq!(select_home(&situation, HomePolicy::InvokingUser),  Ok(Some(PathBuf::from("/home/alice"))));
+        assert_eq!(select_home(&situation, HomePolicy::Error),         Err(HomeError::Ambiguous(situation.clone())));
+
+        situation.elevation = None;
+        situation.home_var  = Some(PathBuf::from("/root"));
+        assert_eq!(select_home(&situation, HomePolicy::Error), Ok(None));
+    }
+
+    #[test]
+    fn test_check_home() {
+        use std::env;
+        use std::path::Path;
+
+        assert_eq!(HomeProblem::check(Path::new("/")),                         Some(HomeProblem::RootDir));
+        assert_eq!(HomeProblem::check(Path::new("/directories-no-such-home")), Some(HomeProblem::Nonexistent));
+        assert_eq!(HomeProblem::check(&env::current_exe().unwrap()),           Some(HomeProblem::NotADirectory));
+        assert_eq!(HomeProblem::check(&env::temp_dir()),                       None);
+    }
+
+    #[test]
+    fn test_relative_root() {
+        assert_eq!(BaseDirs::with_home_fallback(HomeFallback::Root(PathBuf::from("home"))).err(), Some(HomeError::RelativeRoot));
+        assert_eq!(BaseDirs::with_home_fallback(HomeFallback::Root(PathBuf::new())).err(),        Some(HomeError::RelativeRoot));
+    }
+
+    #[test]
+    fn test_temp_root_is_removed() {
+        let temp_root = super::create_temp_root().unwrap();
+        let dirs      = ::FallbackDirs { base_dirs: ::Platform::current().base_dirs(&temp_root), problem: Some(HomeProblem::Unknown), temp_root: Some(temp_root.clone()) };
+        assert!(dirs.is_temporary() && temp_root.is_dir());
+        drop(dirs);
+        assert!(!temp_root.exists());
+    }
+}
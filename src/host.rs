@@ -0,0 +1,236 @@
+#[cfg(unix)]
+extern crate libc;
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use probe;
+use DirKind;
+use FileSystemKind;
+use ProjectDirs;
+use Redirection;
+
+/// `HostKey` identifies the current machine in the paths of host-local directories, e. g. `~/.cache/barapp/hosts/<key>`.
+///
+/// Keys are valid path components: characters other than ASCII letters, digits, `-`, `_` and `.` are replaced by `_`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HostKey {
+    value: String
+}
+
+/// `HostLocal` selects how [`ProjectDirs::host_local`] separates the directories of different machines.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HostLocal {
+    /// Use a subdirectory `hosts/<key>` of each directory.
+    Namespace(HostKey),
+    /// Use a subdirectory of the given directory on a local disk, named after the kind of the directory, e. g. `<dir>/cache`.
+    Dir(PathBuf)
+}
+
+impl HostKey {
+    /// Creates a host key from the given value, e. g. a name from a configuration file or a fixed value in tests.
+    ///
+    /// Returns `None` if the value is empty or consists only of dots after leading and trailing whitespace is removed.
+    pub fn new(value: &str) -> Option<HostKey> {
+        let value: String = value.trim().chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '_' }).collect();
+        if value.chars().all(|c| c == '.') {
+            return None;
+        }
+        Some(HostKey { value })
+    }
+    /// Returns a host key derived from the host name of the machine.
+    ///
+    /// On Windows, the host name is read from `%COMPUTERNAME%`.
+    pub fn hostname() -> Option<HostKey> {
+        hostname().and_then(|hostname| HostKey::new(&hostname))
+    }
+    /// Returns a host key derived from the machine ID in `/etc/machine-id` or `/var/lib/dbus/machine-id`,
+    /// which, unlike the host name, is unique and stable across renames.
+    ///
+    /// Returns `None` on platforms other than Linux.
+    pub fn machine_id() -> Option<HostKey> {
+        if !cfg!(any(target_os = "linux", target_os = "android")) {
+            return None;
+        }
+        ["/etc/machine-id", "/var/lib/dbus/machine-id"].iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .find_map(|machine_id| HostKey::new(&machine_id))
+    }
+    /// Returns the host key of the current machine: [`HostKey::machine_id`] if available, and [`HostKey::hostname`] otherwise.
+    pub fn detect() -> Option<HostKey> {
+        HostKey::machine_id().or_else(HostKey::hostname)
+    }
+    /// Returns the key as a string.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for HostKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+#[cfg(unix)]
+fn hostname() -> Option<String> {
+    // POSIX does not guarantee a terminating NUL if the name was truncated, so the last byte of the buffer
+    // is never written, and the name ends at the first NUL.
+    let mut buf = [0u8; 256];
+    if unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len() - 1) } != 0 {
+        return None;
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    Some(String::from_utf8_lossy(&buf[..len]).into_owned())
+}
+
+#[cfg(not(unix))]
+fn hostname() -> Option<String> {
+    ::std::env::var("COMPUTERNAME").ok()
+}
+
+impl ProjectDirs {
+    /// Returns whether the directory of the given kind, or its nearest existing ancestor, is located on a network filesystem,
+    /// e. g. NFS or SMB. Returns `false` if the directory is not available or its filesystem could not be determined.
+    ///
+    /// The filesystem is determined with `statfs` on Linux, macOS and FreeBSD; on other platforms, this always returns `false`.
+    pub fn is_on_network_file_system(&self, kind: DirKind) -> bool {
+        self.entries().iter().find(|&&(k, _)| k == kind).and_then(|&(_, dir)| dir).map_or(false, is_on_network_file_system)
+    }
+    /// Returns a copy of this struct where the cache and state directories are separated per machine,
+    /// if they are located on a network filesystem, together with the redirections which were applied.
+    ///
+    /// Home directories shared by several machines over NFS or SMB cause lock files and databases in these directories
+    /// to be written concurrently from different hosts. Directories on local filesystems are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::{HostKey, HostLocal, ProjectDirs};
+    /// if let (Some(proj_dirs), Some(key)) = (ProjectDirs::from("com", "Foo Corp", "Bar App"), HostKey::hostname()) {
+    ///     let (proj_dirs, _) = proj_dirs.host_local(&HostLocal::Namespace(key));
+    ///     proj_dirs.cache_dir();
+    ///     // Linux, on NFS: /home/alice/.cache/barapp/hosts/workstation-1
+    ///     // Linux, local:  /home/alice/.cache/barapp
+    /// }
+    /// ```
+    pub fn host_local(&self, mode: &HostLocal) -> (ProjectDirs, Vec<Redirection>) {
+        host_local(self, mode, &is_on_network_file_system)
+    }
+    /// Returns the directory for configuration files specific to the machine identified by `key`,
+    /// i. e. `<config_dir>/hosts/<key>`, for home directories shared by several machines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::{HostKey, ProjectDirs};
+    /// if let (Some(proj_dirs), Some(key)) = (ProjectDirs::from("com", "Foo Corp", "Bar App"), HostKey::detect()) {
+    ///     proj_dirs.host_config_dir(&key);
+    ///     // Linux: /home/alice/.config/barapp/hosts/4f1c2a0b9d3e4e7f8a6b5c4d3e2f1a0b
+    /// }
+    /// ```
+    pub fn host_config_dir(&self, key: &HostKey) -> PathBuf {
+        self.config_dir().join("hosts").join(key.as_str())
+    }
+    /// Returns the path of the configuration file `file`, looking in the directory returned by
+    /// [`ProjectDirs::host_config_dir`] first and in the config directory second,
+    /// or `None` if the file exists in neither of them.
+    ///
+    /// If `key` is `None`, only the config directory is searched.
+    pub fn find_config_file<P: AsRef<Path>>(&self, key: Option<&HostKey>, file: P) -> Option<PathBuf> {
+        let file = file.as_ref();
+        key.map(|key| self.host_config_dir(key))
+            .into_iter()
+            .chain(Some(self.config_dir().to_path_buf()))
+            .map(|dir| dir.join(file))
+            .find(|path| path.exists())
+    }
+}
+
+fn is_on_network_file_system(path: &Path) -> bool {
+    probe::nearest_existing(path).and_then(probe::file_system).map_or(false, |file_system| file_system.kind == FileSystemKind::Network)
+}
+
+fn host_local(proj_dirs: &ProjectDirs, mode: &HostLocal, is_on_network: &dyn Fn(&Path) -> bool) -> (ProjectDirs, Vec<Redirection>) {
+    let mut local        = proj_dirs.clone();
+    let mut redirections = Vec::new();
+    for &(kind, dir) in &[(DirKind::Cache, Some(proj_dirs.cache_dir())), (DirKind::State, proj_dirs.state_dir())] {
+        let original = match dir {
+            Some(dir) if is_on_network(dir) => dir,
+            _                               => continue
+        };
+        let path = match *mode {
+            HostLocal::Namespace(ref key) => original.join("hosts").join(key.as_str()),
+            HostLocal::Dir(ref dir)       => dir.join(kind.name().to_ascii_lowercase())
+        };
+        match kind {
+            DirKind::Cache => local.cache_dir = Arc::from(path.as_path()),
+            _              => local.state_dir = Some(Arc::from(path.as_path()))
+        }
+        redirections.push(Redirection { kind, original: original.to_path_buf(), redirected: path });
+    }
+    (local, redirections)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use super::host_local;
+    use HostKey;
+    use HostLocal;
+    use Platform;
+
+    #[test]
+    fn test_host_key() {
+        assert_eq!(HostKey::new(" work station/1\n").unwrap().as_str(), "work_station_1");
+        assert_eq!(HostKey::new(".."), None);
+        assert_eq!(HostKey::new(""),   None);
+    }
+
+    #[test]
+    fn test_find_config_file() {
+        use std::fs;
+
+        use test_util::TempDir;
+
+        let temp_dir  = TempDir::new("host");
+        let base_dirs = Platform::Linux.base_dirs(&temp_dir);
+        let proj_dirs = Platform::Linux.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        let key       = HostKey::new("ws1").unwrap();
+        fs::create_dir_all(proj_dirs.host_config_dir(&key)).unwrap();
+        fs::write(proj_dirs.config_dir().join("a.toml"), "").unwrap();
+        fs::write(proj_dirs.config_dir().join("b.toml"), "").unwrap();
+        fs::write(proj_dirs.host_config_dir(&key).join("b.toml"), "").unwrap();
+
+        assert_eq!(proj_dirs.host_config_dir(&key), proj_dirs.config_dir().join("hosts").join("ws1"));
+        assert_eq!(proj_dirs.find_config_file(Some(&key), "a.toml"), Some(proj_dirs.config_dir().join("a.toml")));
+        assert_eq!(proj_dirs.find_config_file(Some(&key), "b.toml"), Some(proj_dirs.host_config_dir(&key).join("b.toml")));
+        assert_eq!(proj_dirs.find_config_file(None, "b.toml"),       Some(proj_dirs.config_dir().join("b.toml")));
+        assert_eq!(proj_dirs.find_config_file(Some(&key), "c.toml"), None);
+    }
+
+    #[test]
+    fn test_host_local() {
+        let base_dirs  = Platform::Linux.base_dirs(Path::new("/home/alice"));
+        let proj_dirs  = Platform::Linux.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        let on_network = |path: &Path| path.starts_with("/home/alice");
+
+        let (local, redirections) = host_local(&proj_dirs, &HostLocal::Namespace(HostKey::new("ws1").unwrap()), &on_network);
+        assert_eq!(redirections.len(), 2);
+        assert_eq!(local.cache_dir(),  Path::new("/home/alice/.cache/barapp/hosts/ws1"));
+        assert_eq!(local.state_dir(),  Some(Path::new("/home/alice/.local/state/barapp/hosts/ws1")));
+        assert_eq!(local.config_dir(), proj_dirs.config_dir());
+
+        let (local, _) = host_local(&proj_dirs, &HostLocal::Dir(PathBuf::from("/var/tmp/alice-barapp")), &on_network);
+        assert_eq!(local.cache_dir(), Path::new("/var/tmp/alice-barapp/cache"));
+
+        let (local, redirections) = host_local(&proj_dirs, &HostLocal::Dir(PathBuf::from("/var/tmp/alice-barapp")), &|_: &Path| false);
+        assert!(redirections.is_empty());
+        assert_eq!(local, proj_dirs);
+    }
+}
@@ -14,8 +14,105 @@
 
 #![deny(missing_docs)]
 
+use std::error;
+use std::fmt;
+use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
+
+#[cfg(feature = "macros")]
+extern crate directories_macros;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+
+mod app_id;
+mod classify;
+mod contract;
+mod diff;
+mod dir_kind;
+mod doctor;
+mod expand;
+mod export;
+mod for_user;
+mod global;
+mod home;
+mod host;
+mod nested;
+mod org;
+mod passwd;
+mod platform;
+mod probe;
+mod redirect;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod symbolic;
+#[cfg(test)]
+mod test_util;
+
+pub use app_id::AppId;
+pub use app_id::AppIdError;
+pub use diff::DirChange;
+pub use dir_kind::DirKind;
+pub use dir_kind::DirLookup;
+pub use doctor::Diagnosis;
+pub use doctor::DirDiagnosis;
+pub use doctor::DirWarning;
+pub use doctor::IgnoredVar;
+pub use doctor::IgnoredVarReason;
+pub use expand::ExpandError;
+pub use export::ExportError;
+pub use export::Shell;
+pub use global::global;
+pub use global::refresh;
+pub use global::Snapshot;
+pub use home::Elevation;
+pub use home::FallbackDirs;
+pub use home::HomeError;
+pub use home::HomeFallback;
+pub use home::HomePolicy;
+pub use home::HomeProblem;
+pub use home::HomeSituation;
+pub use host::HostKey;
+pub use host::HostLocal;
+pub use org::OrgDirs;
+pub use platform::Platform;
+pub use probe::FileSystem;
+pub use probe::FileSystemKind;
+pub use redirect::RedirectTarget;
+pub use redirect::Redirection;
+pub use symbolic::SymbolicPath;
+pub use symbolic::SymbolicPathError;
+
+/// Creates a `ProjectDirs` struct from the metadata of the Cargo package being compiled.
+///
+/// Requires the `macros` feature. The application name is taken from the package name, the qualifier and organization
+/// from the optional keys of the package's `[package.metadata.directories]` table:
+///
+/// ```toml
+/// [package.metadata.directories]
+/// qualifier    = "com"
+/// organization = "FooCorp"
+/// ```
+///
+/// Compilation fails if a name is invalid or would be adapted differently on some platform,
+/// e. g. because it contains whitespace or the package name contains uppercase letters.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate directories;
+///
+/// fn main() {
+///     if let Some(proj_dirs) = project_dirs!() {
+///         proj_dirs.config_dir();
+///     }
+/// }
+/// ```
+#[cfg(feature = "macros")]
+pub use directories_macros::project_dirs;
 
 #[cfg(target_os = "windows")]
 mod win;
@@ -59,21 +156,21 @@ use lin as sys;
 ///     // macOS:   /Users/Alice/Library/Application Support
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BaseDirs {
     // home directory
-    home_dir:       PathBuf,
+    home_dir:       Arc<Path>,
 
     // base directories
-    cache_dir:        PathBuf,
-    config_dir:       PathBuf,
-    config_local_dir: PathBuf,
-    data_dir:         PathBuf,
-    data_local_dir:   PathBuf,
-    executable_dir:   Option<PathBuf>,
-    preference_dir:   PathBuf,
-    runtime_dir:      Option<PathBuf>,
-    state_dir:        Option<PathBuf>
+    cache_dir:        Arc<Path>,
+    config_dir:       Arc<Path>,
+    config_local_dir: Arc<Path>,
+    data_dir:         Arc<Path>,
+    data_local_dir:   Arc<Path>,
+    executable_dir:   Option<Arc<Path>>,
+    preference_dir:   Arc<Path>,
+    runtime_dir:      Option<Arc<Path>>,
+    state_dir:        Option<Arc<Path>>
 }
 
 /// `UserDirs` provides paths of user-facing standard directories, following the conventions of the operating system the library is running on.
@@ -91,22 +188,22 @@ pub struct BaseDirs {
 ///     // macOS:   /Users/Alice/Music
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct UserDirs {
     // home directory
-    home_dir:     PathBuf,
+    home_dir:     Arc<Path>,
 
     // user directories
-    audio_dir:    Option<PathBuf>,
-    desktop_dir:  Option<PathBuf>,
-    document_dir: Option<PathBuf>,
-    download_dir: Option<PathBuf>,
-    font_dir:     Option<PathBuf>,
-    picture_dir:  Option<PathBuf>,
-    public_dir:   Option<PathBuf>,
-    template_dir: Option<PathBuf>,
+    audio_dir:    Option<Arc<Path>>,
+    desktop_dir:  Option<Arc<Path>>,
+    document_dir: Option<Arc<Path>>,
+    download_dir: Option<Arc<Path>>,
+    font_dir:     Option<Arc<Path>>,
+    picture_dir:  Option<Arc<Path>>,
+    public_dir:   Option<Arc<Path>>,
+    template_dir: Option<Arc<Path>>,
     // trash_dir:    PathBuf,
-    video_dir:    Option<PathBuf>
+    video_dir:    Option<Arc<Path>>
 }
 
 /// `ProjectDirs` computes the location of cache, config or data directories for a specific application,
@@ -126,19 +223,19 @@ pub struct UserDirs {
 ///     // macOS:   /Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProjectDirs {
-    project_path:     PathBuf,
+    project_path:     Arc<Path>,
 
     // base directories
-    cache_dir:        PathBuf,
-    config_dir:       PathBuf,
-    config_local_dir: PathBuf,
-    data_dir:         PathBuf,
-    data_local_dir:   PathBuf,
-    preference_dir:   PathBuf,
-    runtime_dir:      Option<PathBuf>,
-    state_dir:        Option<PathBuf>
+    cache_dir:        Arc<Path>,
+    config_dir:       Arc<Path>,
+    config_local_dir: Arc<Path>,
+    data_dir:         Arc<Path>,
+    data_local_dir:   Arc<Path>,
+    preference_dir:   Arc<Path>,
+    runtime_dir:      Option<Arc<Path>>,
+    state_dir:        Option<Arc<Path>>
 }
 
 impl BaseDirs {
@@ -178,7 +275,7 @@ impl BaseDirs {
     /// | macOS   | `$HOME`              | /Users/Alice   |
     /// | Windows | `{FOLDERID_Profile}` | C:\Users\Alice |
     pub fn home_dir(&self) -> &Path {
-        self.home_dir.as_path()
+        &self.home_dir
     }
     /// Returns the path to the user's cache directory.
     ///
@@ -188,7 +285,7 @@ impl BaseDirs {
     /// | macOS   | `$HOME`/Library/Caches              | /Users/Alice/Library/Caches  |
     /// | Windows | `{FOLDERID_LocalAppData}`           | C:\Users\Alice\AppData\Local |
     pub fn cache_dir(&self) -> &Path {
-        self.cache_dir.as_path()
+        &self.cache_dir
     }
     /// Returns the path to the user's config directory.
     ///
@@ -198,7 +295,7 @@ impl BaseDirs {
     /// | macOS   | `$HOME`/Library/Application Support   | /Users/Alice/Library/Application Support |
     /// | Windows | `{FOLDERID_RoamingAppData}`           | C:\Users\Alice\AppData\Roaming           |
     pub fn config_dir(&self) -> &Path {
-        self.config_dir.as_path()
+        &self.config_dir
     }
     /// Returns the path to the user's local config directory.
     ///
@@ -208,7 +305,7 @@ impl BaseDirs {
     /// | macOS   | `$HOME`/Library/Application Support   | /Users/Alice/Library/Application Support |
     /// | Windows | `{FOLDERID_LocalAppData}`           | C:\Users\Alice\AppData\Local               |
     pub fn config_local_dir(&self) -> &Path {
-        self.config_local_dir.as_path()
+        &self.config_local_dir
     }
     /// Returns the path to the user's data directory.
     ///
@@ -218,7 +315,7 @@ impl BaseDirs {
     /// | macOS   | `$HOME`/Library/Application Support      | /Users/Alice/Library/Application Support |
     /// | Windows | `{FOLDERID_RoamingAppData}`              | C:\Users\Alice\AppData\Roaming           |
     pub fn data_dir(&self) -> &Path {
-        self.data_dir.as_path()
+        &self.data_dir
     }
     /// Returns the path to the user's local data directory.
     ///
@@ -228,7 +325,7 @@ impl BaseDirs {
     /// | macOS   | `$HOME`/Library/Application Support      | /Users/Alice/Library/Application Support |
     /// | Windows | `{FOLDERID_LocalAppData}`                | C:\Users\Alice\AppData\Local             |
     pub fn data_local_dir(&self) -> &Path {
-        self.data_local_dir.as_path()
+        &self.data_local_dir
     }
     /// Returns the path to the user's executable directory.
     ///
@@ -238,7 +335,7 @@ impl BaseDirs {
     /// | macOS   | –                                                                | –                      |
     /// | Windows | –                                                                | –                      |
     pub fn executable_dir(&self) -> Option<&Path> {
-        self.executable_dir.as_ref().map(|p| p.as_path())
+        self.executable_dir.as_deref()
     }
     /// Returns the path to the user's preference directory.
     ///
@@ -248,7 +345,7 @@ impl BaseDirs {
     /// | macOS   | `$HOME`/Library/Preferences           | /Users/Alice/Library/Preferences |
     /// | Windows | `{FOLDERID_RoamingAppData}`           | C:\Users\Alice\AppData\Roaming   |
     pub fn preference_dir(&self) -> &Path {
-        self.preference_dir.as_path()
+        &self.preference_dir
     }
     /// Returns the path to the user's runtime directory.
     ///
@@ -258,7 +355,7 @@ impl BaseDirs {
     /// | macOS   | –                  | –               |
     /// | Windows | –                  | –               |
     pub fn runtime_dir(&self) -> Option<&Path> {
-        self.runtime_dir.as_ref().map(|p| p.as_path())
+        self.runtime_dir.as_deref()
     }
     /// Returns the path to the user's state directory.
     ///
@@ -274,7 +371,39 @@ impl BaseDirs {
     /// | macOS   | –                                         | –                        |
     /// | Windows | –                                         | –                        |
     pub fn state_dir(&self) -> Option<&Path> {
-        self.state_dir.as_ref().map(|p| p.as_path())
+        self.state_dir.as_deref()
+    }
+    /// Creates a `ProjectDirs` struct from values describing the project, deriving its paths from this `BaseDirs` snapshot.
+    ///
+    /// Unlike [`ProjectDirs::from`], this neither reads environment variables nor queries the operating system again,
+    /// so the returned project directories are guaranteed to be located in the base directories of `self`.
+    ///
+    /// The parameters have the same meaning and are validated the same way as in [`ProjectDirs::try_from`].
+    pub fn project(&self, qualifier: &str, organization: &str, application: &str) -> Result<ProjectDirs, ProjectDirsError> {
+        self.project_from_path(project_path_from(qualifier, organization, application)?)
+    }
+    /// Creates a `ProjectDirs` struct directly from a `PathBuf` value, deriving its paths from this `BaseDirs` snapshot.
+    ///
+    /// Unlike [`ProjectDirs::from_path`], this neither reads environment variables nor queries the operating system again.
+    ///
+    /// The project path is validated the same way as in [`ProjectDirs::try_from_path`].
+    pub fn project_from_path(&self, project_path: PathBuf) -> Result<ProjectDirs, ProjectDirsError> {
+        validate_project_path(&project_path)?;
+        Ok(Platform::current().project_dirs_from_base(self, project_path))
+    }
+    pub(crate) fn entries(&self) -> [(DirKind, Option<&Path>); 10] {
+        [
+            (DirKind::Home,        Some(self.home_dir())),
+            (DirKind::Cache,       Some(self.cache_dir())),
+            (DirKind::Config,      Some(self.config_dir())),
+            (DirKind::ConfigLocal, Some(self.config_local_dir())),
+            (DirKind::Data,        Some(self.data_dir())),
+            (DirKind::DataLocal,   Some(self.data_local_dir())),
+            (DirKind::Executable,  self.executable_dir()),
+            (DirKind::Preference,  Some(self.preference_dir())),
+            (DirKind::Runtime,     self.runtime_dir()),
+            (DirKind::State,       self.state_dir())
+        ]
     }
 }
 
@@ -297,7 +426,7 @@ impl UserDirs {
     /// | macOS   | `$HOME`              | /Users/Alice   |
     /// | Windows | `{FOLDERID_Profile}` | C:\Users\Alice |
     pub fn home_dir(&self) -> &Path {
-        self.home_dir.as_path()
+        &self.home_dir
     }
     /// Returns the path to the user's audio directory.
     ///
@@ -307,7 +436,7 @@ impl UserDirs {
     /// | macOS   | `$HOME`/Music      | /Users/Alice/Music   |
     /// | Windows | `{FOLDERID_Music}` | C:\Users\Alice\Music |
     pub fn audio_dir(&self) -> Option<&Path> {
-        self.audio_dir.as_ref().map(|p| p.as_path())
+        self.audio_dir.as_deref()
     }
     /// Returns the path to the user's desktop directory.
     ///
@@ -317,7 +446,7 @@ impl UserDirs {
     /// | macOS   | `$HOME`/Desktop      | /Users/Alice/Desktop   |
     /// | Windows | `{FOLDERID_Desktop}` | C:\Users\Alice\Desktop |
     pub fn desktop_dir(&self) -> Option<&Path> {
-        self.desktop_dir.as_ref().map(|p| p.as_path())
+        self.desktop_dir.as_deref()
     }
     /// Returns the path to the user's document directory.
     ///
@@ -327,7 +456,7 @@ impl UserDirs {
     /// | macOS   | `$HOME`/Documents      | /Users/Alice/Documents   |
     /// | Windows | `{FOLDERID_Documents}` | C:\Users\Alice\Documents |
     pub fn document_dir(&self) -> Option<&Path> {
-        self.document_dir.as_ref().map(|p| p.as_path())
+        self.document_dir.as_deref()
     }
     /// Returns the path to the user's download directory.
     ///
@@ -337,7 +466,7 @@ impl UserDirs {
     /// | macOS   | `$HOME`/Downloads      | /Users/Alice/Downloads   |
     /// | Windows | `{FOLDERID_Downloads}` | C:\Users\Alice\Downloads |
     pub fn download_dir(&self) -> Option<&Path> {
-        self.download_dir.as_ref().map(|p| p.as_path())
+        self.download_dir.as_deref()
     }
     /// Returns the path to the user's font directory.
     ///
@@ -347,7 +476,7 @@ impl UserDirs {
     /// | macOS   | `$HOME`/Library/Fonts                                | /Users/Alice/Library/Fonts     |
     /// | Windows | –                                                    | –                              |
     pub fn font_dir(&self) -> Option<&Path> {
-        self.font_dir.as_ref().map(|p| p.as_path())
+        self.font_dir.as_deref()
     }
     /// Returns the path to the user's picture directory.
     ///
@@ -357,7 +486,7 @@ impl UserDirs {
     /// | macOS   | `$HOME`/Pictures      | /Users/Alice/Pictures   |
     /// | Windows | `{FOLDERID_Pictures}` | C:\Users\Alice\Pictures |
     pub fn picture_dir(&self) -> Option<&Path> {
-        self.picture_dir.as_ref().map(|p| p.as_path())
+        self.picture_dir.as_deref()
     }
     /// Returns the path to the user's public directory.
     ///
@@ -367,7 +496,7 @@ impl UserDirs {
     /// | macOS   | `$HOME`/Public        | /Users/Alice/Public |
     /// | Windows | `{FOLDERID_Public}`   | C:\Users\Public     |
     pub fn public_dir(&self) -> Option<&Path> {
-        self.public_dir.as_ref().map(|p| p.as_path())
+        self.public_dir.as_deref()
     }
     /// Returns the path to the user's template directory.
     ///
@@ -377,7 +506,7 @@ impl UserDirs {
     /// | macOS   | –                      | –                                                          |
     /// | Windows | `{FOLDERID_Templates}` | C:\Users\Alice\AppData\Roaming\Microsoft\Windows\Templates |
     pub fn template_dir(&self) -> Option<&Path> {
-        self.template_dir.as_ref().map(|p| p.as_path())
+        self.template_dir.as_deref()
     }
     /// Returns the path to the user's video directory.
     ///
@@ -387,26 +516,64 @@ impl UserDirs {
     /// | macOS   | `$HOME`/Movies      | /Users/Alice/Movies   |
     /// | Windows | `{FOLDERID_Videos}` | C:\Users\Alice\Videos |
     pub fn video_dir(&self) -> Option<&Path> {
-        self.video_dir.as_ref().map(|p| p.as_path())
+        self.video_dir.as_deref()
+    }
+    pub(crate) fn entries(&self) -> [(DirKind, Option<&Path>); 10] {
+        [
+            (DirKind::Home,     Some(self.home_dir())),
+            (DirKind::Audio,    self.audio_dir()),
+            (DirKind::Desktop,  self.desktop_dir()),
+            (DirKind::Document, self.document_dir()),
+            (DirKind::Download, self.download_dir()),
+            (DirKind::Font,     self.font_dir()),
+            (DirKind::Picture,  self.picture_dir()),
+            (DirKind::Public,   self.public_dir()),
+            (DirKind::Template, self.template_dir()),
+            (DirKind::Video,    self.video_dir())
+        ]
     }
 }
 
 impl ProjectDirs {
     /// Creates a `ProjectDirs` struct directly from a `PathBuf` value.
-    /// The argument is used verbatim and is not adapted to operating system standards.
+    /// The argument is not adapted to operating system standards.
+    ///
+    /// The returned value is either
+    /// - `Some`, containing project directory paths based on the state of the system's paths at the time `from_path()` was invoked, or
+    /// - `None`, if the project path is rejected by [`ProjectDirs::try_from_path`] or no valid home directory path could be retrieved from the operating system.
     ///
     /// The use of `ProjectDirs::from_path` is strongly discouraged, as its results will
     /// not follow operating system standards on at least two of three platforms.
-    /// 
+    ///
     /// Use [`ProjectDirs::from`] instead.
     pub fn from_path(project_path: PathBuf) -> Option<ProjectDirs> {
+        ProjectDirs::try_from_path(project_path).ok()
+    }
+    /// Creates a `ProjectDirs` struct directly from a `PathBuf` value, reporting why the value was rejected.
+    ///
+    /// The project path is rejected if
+    /// - it is absolute or has a prefix (like `C:`), as it would replace the base directories entirely ([`ProjectDirsError::AbsolutePath`]),
+    /// - it contains a `..` component, as it would escape the base directories ([`ProjectDirsError::ParentDirComponent`]), or
+    /// - it has no non-blank components, as the project directories would coincide with the base directories ([`ProjectDirsError::EmptyPath`]).
+    ///
+    /// If no valid home directory path could be retrieved from the operating system, [`ProjectDirsError::NoHomeDir`] is returned.
+    pub fn try_from_path(project_path: PathBuf) -> Result<ProjectDirs, ProjectDirsError> {
+        validate_project_path(&project_path)?;
+        sys::project_dirs_from_path(project_path).ok_or(ProjectDirsError::NoHomeDir)
+    }
+    /// Creates a `ProjectDirs` struct directly from a `PathBuf` value without validating it.
+    /// The argument is used verbatim, so absolute paths, `..` components and empty paths are accepted.
+    ///
+    /// This retains the lenient behavior of earlier versions of `ProjectDirs::from_path`;
+    /// use [`ProjectDirs::from_path`] or [`ProjectDirs::try_from_path`] unless that behavior is required.
+    pub fn from_path_unchecked(project_path: PathBuf) -> Option<ProjectDirs> {
         sys::project_dirs_from_path(project_path)
     }
     /// Creates a `ProjectDirs` struct from values describing the project.
     ///
     /// The returned value depends on the operating system and is either
     /// - `Some`, containing project directory paths based on the state of the system's paths at the time `new()` was invoked, or
-    /// - `None`, if no valid home directory path could be retrieved from the operating system.
+    /// - `None`, if the values are rejected by [`ProjectDirs::try_from`] or no valid home directory path could be retrieved from the operating system.
     ///
     /// To determine whether a system provides a valid `$HOME` path, please refer to [`BaseDirs::new`]
     ///
@@ -421,17 +588,42 @@ impl ProjectDirs {
     /// - `organization` – The name of the organization that develops this application, or for which the application is developed.<br/>
     ///   An empty string can be passed if no organization should be used (only affects macOS and Windows).<br/>
     ///   Example values: `"Foo Corp"`, `"Alice and Bob Inc"`, `""`
-    /// - `application`  – The name of the application itself.<br/>
+    /// - `application`  – The name of the application itself. It must not be empty or consist only of whitespace.<br/>
     ///   Example values: `"Bar App"`, `"ExampleProgram"`, `"Unicorn-Programme"`
     ///
     /// [`BaseDirs::home_dir`]: struct.BaseDirs.html#method.home_dir
     pub fn from(qualifier: &str, organization: &str, application: &str) -> Option<ProjectDirs> {
-        sys::project_dirs_from(qualifier, organization, application)
+        ProjectDirs::try_from(qualifier, organization, application).ok()
+    }
+    /// Creates a `ProjectDirs` struct from values describing the project, reporting why the values were rejected.
+    ///
+    /// The values are rejected with [`ProjectDirsError::EmptyApplication`] if `application` is empty or consists only of whitespace.
+    /// The project path computed from the values is then validated like in [`ProjectDirs::try_from_path`],
+    /// which rejects e. g. an `organization` of `".."` on Windows.
+    pub fn try_from(qualifier: &str, organization: &str, application: &str) -> Result<ProjectDirs, ProjectDirsError> {
+        ProjectDirs::try_from_path(project_path_from(qualifier, organization, application)?)
+    }
+    /// Creates a `ProjectDirs` struct from an [`AppId`], which has already been validated.
+    ///
+    /// The returned value is either
+    /// - `Some`, containing project directory paths based on the state of the system's paths at the time `from_app_id()` was invoked, or
+    /// - `None`, if no valid home directory path could be retrieved from the operating system.
+    ///
+    /// The project path is the value returned by [`AppId::project_path`].
+    pub fn from_app_id(app_id: &AppId) -> Option<ProjectDirs> {
+        ProjectDirs::from_path(app_id.project_path())
+    }
+    /// Creates a `ProjectDirs` struct from values describing the project without validating them.
+    ///
+    /// This retains the lenient behavior of earlier versions of `ProjectDirs::from`;
+    /// use [`ProjectDirs::from`] or [`ProjectDirs::try_from`] unless that behavior is required.
+    pub fn from_unchecked(qualifier: &str, organization: &str, application: &str) -> Option<ProjectDirs> {
+        sys::project_dirs_from_path(sys::project_path_from(qualifier, organization, application))
     }
     /// Returns the project path fragment used to compute the project's cache/config/data directories.
     /// The value is derived from the `ProjectDirs::from` call and is platform-dependent.
     pub fn project_path(&self) -> &Path {
-        self.project_path.as_path()
+        &self.project_path
     }
     /// Returns the path to the project's cache directory.
     ///
@@ -441,7 +633,7 @@ impl ProjectDirs {
     /// | macOS   | `$HOME`/Library/Caches/`_project_path_`                               | /Users/Alice/Library/Caches/com.Foo-Corp.Bar-App    |
     /// | Windows | `{FOLDERID_LocalAppData}`\\`_project_path_`\\cache                    | C:\Users\Alice\AppData\Local\Foo Corp\Bar App\cache |
     pub fn cache_dir(&self) -> &Path {
-        self.cache_dir.as_path()
+        &self.cache_dir
     }
     /// Returns the path to the project's config directory.
     ///
@@ -451,7 +643,7 @@ impl ProjectDirs {
     /// | macOS   | `$HOME`/Library/Application Support/`_project_path_`                    | /Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App  |
     /// | Windows | `{FOLDERID_RoamingAppData}`\\`_project_path_`\\config                   | C:\Users\Alice\AppData\Roaming\Foo Corp\Bar App\config         |
     pub fn config_dir(&self) -> &Path {
-        self.config_dir.as_path()
+        &self.config_dir
     }
     /// Returns the path to the project's local config directory.
     ///
@@ -461,7 +653,7 @@ impl ProjectDirs {
     /// | macOS   | `$HOME`/Library/Application Support/`_project_path_`                    | /Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App  |
     /// | Windows | `{FOLDERID_LocalAppData}`\\`_project_path_`\\config                     | C:\Users\Alice\AppData\Local\Foo Corp\Bar App\config           |
     pub fn config_local_dir(&self) -> &Path {
-        self.config_local_dir.as_path()
+        &self.config_local_dir
     }
     /// Returns the path to the project's data directory.
     ///
@@ -471,7 +663,7 @@ impl ProjectDirs {
     /// | macOS   | `$HOME`/Library/Application Support/`_project_path_`                       | /Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App |
     /// | Windows | `{FOLDERID_RoamingAppData}`\\`_project_path_`\\data                        | C:\Users\Alice\AppData\Roaming\Foo Corp\Bar App\data          |
     pub fn data_dir(&self) -> &Path {
-        self.data_dir.as_path()
+        &self.data_dir
     }
     /// Returns the path to the project's local data directory.
     ///
@@ -481,7 +673,7 @@ impl ProjectDirs {
     /// | macOS   | `$HOME`/Library/Application Support/`_project_path_`                       | /Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App |
     /// | Windows | `{FOLDERID_LocalAppData}`\\`_project_path_`\\data                          | C:\Users\Alice\AppData\Local\Foo Corp\Bar App\data            |
     pub fn data_local_dir(&self) -> &Path {
-        self.data_local_dir.as_path()
+        &self.data_local_dir
     }
     /// Returns the path to the project's preference directory.
     ///
@@ -491,7 +683,7 @@ impl ProjectDirs {
     /// | macOS   | `$HOME`/Library/Preferences/`_project_path_`                            | /Users/Alice/Library/Preferences/com.Foo-Corp.Bar-App  |
     /// | Windows | `{FOLDERID_RoamingAppData}`\\`_project_path_`\\config                   | C:\Users\Alice\AppData\Roaming\Foo Corp\Bar App\config |
     pub fn preference_dir(&self) -> &Path {
-        self.preference_dir.as_path()
+        &self.preference_dir
     }
     /// Returns the path to the project's runtime directory.
     ///
@@ -504,7 +696,7 @@ impl ProjectDirs {
     /// | macOS   | –                                   | –                     |
     /// | Windows | –                                   | –                     |
     pub fn runtime_dir(&self) -> Option<&Path> {
-        self.runtime_dir.as_ref().map(|p| p.as_path())
+        self.runtime_dir.as_deref()
     }
     /// Returns the path to the project's state directory.
     ///
@@ -520,12 +712,84 @@ impl ProjectDirs {
     /// | macOS   | –                                                                           | –                               |
     /// | Windows | –                                                                           | –                               |
     pub fn state_dir(&self) -> Option<&Path> {
-        self.state_dir.as_ref().map(|p| p.as_path())
+        self.state_dir.as_deref()
+    }
+    pub(crate) fn entries(&self) -> [(DirKind, Option<&Path>); 8] {
+        [
+            (DirKind::Cache,       Some(self.cache_dir())),
+            (DirKind::Config,      Some(self.config_dir())),
+            (DirKind::ConfigLocal, Some(self.config_local_dir())),
+            (DirKind::Data,        Some(self.data_dir())),
+            (DirKind::DataLocal,   Some(self.data_local_dir())),
+            (DirKind::Preference,  Some(self.preference_dir())),
+            (DirKind::Runtime,     self.runtime_dir()),
+            (DirKind::State,       self.state_dir())
+        ]
+    }
+}
+
+/// The reason why a project path or the values describing a project were rejected by
+/// [`ProjectDirs::try_from`] or [`ProjectDirs::try_from_path`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProjectDirsError {
+    /// The application name is empty or consists only of whitespace.
+    EmptyApplication,
+    /// The project path has no non-blank components.
+    EmptyPath,
+    /// The project path is absolute or has a prefix.
+    AbsolutePath,
+    /// The project path contains a `..` component.
+    ParentDirComponent,
+    /// No valid home directory path could be retrieved from the operating system.
+    NoHomeDir
+}
+
+impl fmt::Display for ProjectDirsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            ProjectDirsError::EmptyApplication   => "application name is empty",
+            ProjectDirsError::EmptyPath          => "project path is empty",
+            ProjectDirsError::AbsolutePath       => "project path is absolute",
+            ProjectDirsError::ParentDirComponent => "project path contains a `..` component",
+            ProjectDirsError::NoHomeDir          => "no valid home directory path could be retrieved from the operating system"
+        };
+        f.write_str(message)
+    }
+}
+
+impl error::Error for ProjectDirsError {}
+
+fn project_path_from(qualifier: &str, organization: &str, application: &str) -> Result<PathBuf, ProjectDirsError> {
+    if application.trim().is_empty() {
+        Err(ProjectDirsError::EmptyApplication)
+    } else {
+        Ok(sys::project_path_from(qualifier, organization, application))
+    }
+}
+
+fn validate_project_path(project_path: &Path) -> Result<(), ProjectDirsError> {
+    let mut is_empty = true;
+    for component in project_path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => return Err(ProjectDirsError::AbsolutePath),
+            Component::ParentDir                      => return Err(ProjectDirsError::ParentDirComponent),
+            Component::CurDir                         => {},
+            Component::Normal(name)                   => is_empty &= name.to_string_lossy().trim().is_empty()
+        }
+    }
+    if is_empty {
+        Err(ProjectDirsError::EmptyPath)
+    } else {
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
+    use ProjectDirsError;
+
     #[test]
     fn test_base_dirs() {
         println!("BaseDirs::new())\n{:?}", ::BaseDirs::new());
@@ -545,4 +809,28 @@ mod tests {
         let proj_dirs = ::ProjectDirs::from("com", "Foo Corp.", "Bar App");
         println!("ProjectDirs::from(\"com\", \"Foo Corp.\", \"Bar App\")\n{:?}", proj_dirs);
     }
+
+    #[test]
+    fn test_base_dirs_project() {
+        if let Some(base_dirs) = ::BaseDirs::new() {
+            let proj_dirs = base_dirs.project("com", "Foo Corp", "Bar App").unwrap();
+            assert_eq!(::ProjectDirs::from("com", "Foo Corp", "Bar App").unwrap(), proj_dirs);
+            assert!(proj_dirs.cache_dir().starts_with(base_dirs.cache_dir()));
+            assert_eq!(Some(ProjectDirsError::EmptyApplication),   base_dirs.project("com", "Foo Corp", "").err());
+            assert_eq!(Some(ProjectDirsError::ParentDirComponent), base_dirs.project_from_path(PathBuf::from("..")).err());
+        }
+    }
+
+    #[test]
+    fn test_project_dirs_rejects_invalid_values() {
+        assert_eq!(Some(ProjectDirsError::EmptyApplication),   ::ProjectDirs::try_from("", "", "").err());
+        assert_eq!(Some(ProjectDirsError::EmptyApplication),   ::ProjectDirs::try_from("com", "Foo Corp", " \t ").err());
+        assert_eq!(Some(ProjectDirsError::EmptyPath),          ::ProjectDirs::try_from_path(PathBuf::new()).err());
+        assert_eq!(Some(ProjectDirsError::EmptyPath),          ::ProjectDirs::try_from_path(PathBuf::from("./ ")).err());
+        assert_eq!(Some(ProjectDirsError::ParentDirComponent), ::ProjectDirs::try_from_path(PathBuf::from("../../etc")).err());
+        assert_eq!(Some(ProjectDirsError::ParentDirComponent), ::ProjectDirs::try_from_path(PathBuf::from("foo/../../etc")).err());
+        assert_eq!(Some(ProjectDirsError::AbsolutePath),       ::ProjectDirs::try_from_path(PathBuf::from("/etc")).err());
+        assert!(::ProjectDirs::from_path(PathBuf::from("../../etc")).is_none());
+        assert!(::ProjectDirs::from("", "", "").is_none());
+    }
 }
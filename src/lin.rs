@@ -1,9 +1,17 @@
 extern crate dirs_sys;
 
+use std::collections::HashMap;
 use std::env;
+use std::fs;
+use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::SystemTime;
 
 use BaseDirs;
+use Platform;
 use UserDirs;
 use ProjectDirs;
 
@@ -20,16 +28,16 @@ pub fn base_dirs() -> Option<BaseDirs> {
         let executable_dir   = env::var_os("XDG_BIN_HOME")   .and_then(dirs_sys::is_absolute_path).unwrap_or_else(|| home_dir.join(".local/bin"));
 
         let base_dirs = BaseDirs {
-            home_dir:         home_dir,
-            cache_dir:        cache_dir,
-            config_dir:       config_dir,
-            config_local_dir: config_local_dir,
-            data_dir:         data_dir,
-            data_local_dir:   data_local_dir,
-            executable_dir:   Some(executable_dir),
-            preference_dir:   preference_dir,
-            runtime_dir:      runtime_dir,
-            state_dir:        Some(state_dir)
+            home_dir:         home_dir.into(),
+            cache_dir:        cache_dir.into(),
+            config_dir:       config_dir.into(),
+            config_local_dir: config_local_dir.into(),
+            data_dir:         data_dir.into(),
+            data_local_dir:   data_local_dir.into(),
+            executable_dir:   Some(executable_dir.into()),
+            preference_dir:   preference_dir.into(),
+            runtime_dir:      runtime_dir.map(Arc::from),
+            state_dir:        Some(state_dir.into())
         };
         Some(base_dirs)
     } else {
@@ -41,19 +49,19 @@ pub fn user_dirs() -> Option<UserDirs> {
     if let Some(home_dir) = dirs_sys::home_dir() {
         let data_dir  = env::var_os("XDG_DATA_HOME").and_then(dirs_sys::is_absolute_path).unwrap_or_else(|| home_dir.join(".local/share"));
         let font_dir  = data_dir.join("fonts");
-        let mut user_dirs_map = dirs_sys::user_dirs(&home_dir);
+        let entries   = user_dir_entries(&home_dir);
 
         let user_dirs = UserDirs {
-            home_dir:     home_dir,
-            audio_dir:    user_dirs_map.remove("MUSIC"),
-            desktop_dir:  user_dirs_map.remove("DESKTOP"),
-            document_dir: user_dirs_map.remove("DOCUMENTS"),
-            download_dir: user_dirs_map.remove("DOWNLOAD"),
-            font_dir:     Some(font_dir),
-            picture_dir:  user_dirs_map.remove("PICTURES"),
-            public_dir:   user_dirs_map.remove("PUBLICSHARE"),
-            template_dir: user_dirs_map.remove("TEMPLATES"),
-            video_dir:    user_dirs_map.remove("VIDEOS")
+            home_dir:     home_dir.into(),
+            audio_dir:    entries.audio_dir,
+            desktop_dir:  entries.desktop_dir,
+            document_dir: entries.document_dir,
+            download_dir: entries.download_dir,
+            font_dir:     Some(font_dir.into()),
+            picture_dir:  entries.picture_dir,
+            public_dir:   entries.public_dir,
+            template_dir: entries.template_dir,
+            video_dir:    entries.video_dir
         };
         Some(user_dirs)
     } else {
@@ -61,79 +69,133 @@ pub fn user_dirs() -> Option<UserDirs> {
     }
 }
 
-pub fn project_dirs_from_path(project_path: PathBuf) -> Option<ProjectDirs> {
-    if let Some(home_dir)    = dirs_sys::home_dir() {
-        let cache_dir        = env::var_os("XDG_CACHE_HOME") .and_then(dirs_sys::is_absolute_path).unwrap_or_else(|| home_dir.join(".cache")).join(&project_path);
-        let config_dir       = env::var_os("XDG_CONFIG_HOME").and_then(dirs_sys::is_absolute_path).unwrap_or_else(|| home_dir.join(".config")).join(&project_path);
-        let config_local_dir = config_dir.clone();
-        let data_dir         = env::var_os("XDG_DATA_HOME")  .and_then(dirs_sys::is_absolute_path).unwrap_or_else(|| home_dir.join(".local/share")).join(&project_path);
-        let data_local_dir   = data_dir.clone();
-        let preference_dir   = config_dir.clone();
-        let runtime_dir      = env::var_os("XDG_RUNTIME_DIR").and_then(dirs_sys::is_absolute_path).map(|o| o.join(&project_path));
-        let state_dir        = env::var_os("XDG_STATE_HOME") .and_then(dirs_sys::is_absolute_path).unwrap_or_else(|| home_dir.join(".local/state")).join(&project_path);
-
-        let project_dirs = ProjectDirs {
-            project_path:     project_path,
-            cache_dir:        cache_dir,
-            config_dir:       config_dir,
-            config_local_dir: config_local_dir,
-            data_dir:         data_dir,
-            data_local_dir:   data_local_dir,
-            preference_dir:   preference_dir,
-            runtime_dir:      runtime_dir,
-            state_dir:        Some(state_dir)
-        };
-        Some(project_dirs)
-    } else {
-        None
-    }
+// The entries of the most recently parsed user-dirs.dirs file, which are reused as long as
+// the home directory, the path of the file, and its modification time and size do not change.
+static USER_DIRS_CACHE: Mutex<Option<(UserDirsFile, UserDirEntries)>> = Mutex::new(None);
+
+#[derive(PartialEq)]
+struct UserDirsFile {
+    home_dir: PathBuf,
+    path:     PathBuf,
+    modified: Option<SystemTime>,
+    len:      Option<u64>
 }
 
-pub fn project_dirs_from(_qualifier: &str, _organization: &str, application: &str) -> Option<ProjectDirs> {
-    ProjectDirs::from_path(PathBuf::from(&trim_and_lowercase_then_replace_spaces(application, "")))
+#[derive(Clone)]
+struct UserDirEntries {
+    audio_dir:    Option<Arc<Path>>,
+    desktop_dir:  Option<Arc<Path>>,
+    document_dir: Option<Arc<Path>>,
+    download_dir: Option<Arc<Path>>,
+    picture_dir:  Option<Arc<Path>>,
+    public_dir:   Option<Arc<Path>>,
+    template_dir: Option<Arc<Path>>,
+    video_dir:    Option<Arc<Path>>
 }
 
-fn trim_and_lowercase_then_replace_spaces(name: &str, replacement: &str) -> String {
-    let mut buf = String::with_capacity(name.len());
-    let mut parts = name.split_whitespace();
-    let mut current_part = parts.next();
-    let replace = !replacement.is_empty();
-    while current_part.is_some() {
-        let value = current_part.unwrap().to_lowercase();
-        buf.push_str(&value);
-        current_part = parts.next();
-        if replace && current_part.is_some() {
-            buf.push_str(replacement);
+fn user_dir_entries(home_dir: &Path) -> UserDirEntries {
+    // The location `dirs_sys::user_dirs` reads the file from.
+    let path = env::var_os("XDG_CONFIG_HOME").and_then(dirs_sys::is_absolute_path).unwrap_or_else(|| home_dir.join(".config")).join("user-dirs.dirs");
+    cached_user_dir_entries(&USER_DIRS_CACHE, home_dir, &path, &dirs_sys::user_dirs)
+}
+
+fn cached_user_dir_entries(cache: &Mutex<Option<(UserDirsFile, UserDirEntries)>>, home_dir: &Path, path: &Path, user_dirs: &dyn Fn(&Path) -> HashMap<String, PathBuf>) -> UserDirEntries {
+    // The file is inspected before it is parsed: if it changes in between, the next call
+    // sees a different modification time or size and parses it again.
+    let metadata = fs::metadata(path).ok();
+    let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+    let len      = metadata.as_ref().map(|m| m.len());
+
+    let mut cache = cache.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some((ref file, ref entries)) = *cache {
+        if file.home_dir == home_dir && file.path == path && file.modified == modified && file.len == len {
+            return entries.clone();
         }
     }
-    buf
+
+    let mut user_dirs = user_dirs(home_dir);
+    let mut entry     = |key: &str| user_dirs.remove(key).map(Arc::from);
+    let entries       = UserDirEntries {
+        audio_dir:    entry("MUSIC"),
+        desktop_dir:  entry("DESKTOP"),
+        document_dir: entry("DOCUMENTS"),
+        download_dir: entry("DOWNLOAD"),
+        picture_dir:  entry("PICTURES"),
+        public_dir:   entry("PUBLICSHARE"),
+        template_dir: entry("TEMPLATES"),
+        video_dir:    entry("VIDEOS")
+    };
+    let file = UserDirsFile {
+        home_dir: home_dir.to_path_buf(),
+        path:     path.to_path_buf(),
+        modified,
+        len
+    };
+    *cache = Some((file, entries.clone()));
+    entries
+}
+
+pub fn project_dirs_from_path(project_path: PathBuf) -> Option<ProjectDirs> {
+    base_dirs().map(|base_dirs| Platform::Linux.project_dirs_from_base(&base_dirs, project_path))
+}
+
+pub fn project_path_from(qualifier: &str, organization: &str, application: &str) -> PathBuf {
+    Platform::Linux.project_path(qualifier, organization, application)
 }
 
 #[cfg(test)]
 mod tests {
-    use lin::trim_and_lowercase_then_replace_spaces;
+    use std::sync::Arc;
 
     #[test]
-    fn test_trim_and_lowercase_then_replace_spaces() {
-        let input1    = "Bar App";
-        let actual1   = trim_and_lowercase_then_replace_spaces(input1, "-");
-        let expected1 = "bar-app";
-        assert_eq!(expected1, actual1);
-
-        let input2    = "BarApp-Foo";
-        let actual2   = trim_and_lowercase_then_replace_spaces(input2, "-");
-        let expected2 = "barapp-foo";
-        assert_eq!(expected2, actual2);
-
-        let input3    = " Bar App ";
-        let actual3   = trim_and_lowercase_then_replace_spaces(input3, "-");
-        let expected3 = "bar-app";
-        assert_eq!(expected3, actual3);
-
-        let input4    = "  Bar  App  ";
-        let actual4   = trim_and_lowercase_then_replace_spaces(input4, "-");
-        let expected4 = "bar-app";
-        assert_eq!(expected4, actual4);
+    fn test_user_dirs_are_cached() {
+        use std::cell::Cell;
+        use std::collections::HashMap;
+        use std::ffi::CString;
+        use std::fs;
+        use std::os::unix::ffi::OsStrExt;
+        use std::path::Path;
+        use std::sync::Mutex;
+
+        use super::cached_user_dir_entries;
+        use test_util::TempDir;
+
+        let home_dir = TempDir::new("user-dirs");
+        let path     = home_dir.join("user-dirs.dirs");
+        let cache    = Mutex::new(None);
+        fs::create_dir_all(&home_dir).unwrap();
+        fs::write(&path, "Docs").unwrap();
+
+        // Stands in for `dirs_sys::user_dirs`, counting how often the file is parsed.
+        let parses    = Cell::new(0);
+        let user_dirs = |home_dir: &Path| {
+            parses.set(parses.get() + 1);
+            let mut user_dirs = HashMap::new();
+            user_dirs.insert("DOCUMENTS".to_string(), home_dir.join(fs::read_to_string(&path).unwrap()));
+            user_dirs
+        };
+
+        let entries1 = cached_user_dir_entries(&cache, &home_dir, &path, &user_dirs);
+        let entries2 = cached_user_dir_entries(&cache, &home_dir, &path, &user_dirs);
+        assert_eq!(entries1.document_dir.as_deref(), Some(home_dir.join("Docs").as_path()));
+        assert!(Arc::ptr_eq(entries1.document_dir.as_ref().unwrap(), entries2.document_dir.as_ref().unwrap()));
+        assert_eq!(parses.get(), 1);
+
+        // A change of the size invalidates the cache.
+        fs::write(&path, "Documents").unwrap();
+        let entries3 = cached_user_dir_entries(&cache, &home_dir, &path, &user_dirs);
+        assert_eq!(entries3.document_dir.as_deref(), Some(home_dir.join("Documents").as_path()));
+        assert_eq!(parses.get(), 2);
+
+        // A change of the modification time invalidates the cache, even if the size is the same.
+        fs::write(&path, "Dokuments").unwrap();
+        let c_path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        let times  = [libc::timeval { tv_sec: 1_000_000_000, tv_usec: 0 }; 2];
+        assert_eq!(unsafe { libc::utimes(c_path.as_ptr(), times.as_ptr()) }, 0);
+        let entries4 = cached_user_dir_entries(&cache, &home_dir, &path, &user_dirs);
+        assert_eq!(entries4.document_dir.as_deref(), Some(home_dir.join("Dokuments").as_path()));
+        cached_user_dir_entries(&cache, &home_dir, &path, &user_dirs);
+        assert_eq!(parses.get(), 3);
     }
 
     #[test]
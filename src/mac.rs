@@ -1,8 +1,10 @@
 extern crate dirs_sys;
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use BaseDirs;
+use Platform;
 use UserDirs;
 use ProjectDirs;
 
@@ -16,14 +18,14 @@ pub fn base_dirs() -> Option<BaseDirs> {
         let preference_dir   = home_dir.join("Library/Preferences");
 
         let base_dirs = BaseDirs {
-            home_dir:         home_dir,
-            cache_dir:        cache_dir,
-            config_dir:       config_dir,
-            config_local_dir: config_local_dir,
-            data_dir:         data_dir,
-            data_local_dir:   data_local_dir,
+            home_dir:         home_dir.into(),
+            cache_dir:        cache_dir.into(),
+            config_dir:       config_dir.into(),
+            config_local_dir: config_local_dir.into(),
+            data_dir:         data_dir.into(),
+            data_local_dir:   data_local_dir.into(),
             executable_dir:   None,
-            preference_dir:   preference_dir,
+            preference_dir:   preference_dir.into(),
             runtime_dir:      None,
             state_dir:        None
         };
@@ -45,16 +47,16 @@ pub fn user_dirs() -> Option<UserDirs> {
         let font_dir      = home_dir.join("Library/Fonts");
 
         let user_dirs = UserDirs {
-            home_dir:     home_dir,
-            audio_dir:    Some(audio_dir),
-            desktop_dir:  Some(desktop_dir),
-            document_dir: Some(document_dir),
-            download_dir: Some(download_dir),
-            font_dir:     Some(font_dir),
-            picture_dir:  Some(picture_dir),
-            public_dir:   Some(public_dir),
+            home_dir:     home_dir.into(),
+            audio_dir:    Some(audio_dir.into()),
+            desktop_dir:  Some(desktop_dir.into()),
+            document_dir: Some(document_dir.into()),
+            download_dir: Some(download_dir.into()),
+            font_dir:     Some(font_dir.into()),
+            picture_dir:  Some(picture_dir.into()),
+            public_dir:   Some(public_dir.into()),
             template_dir: None,
-            video_dir:    Some(video_dir)
+            video_dir:    Some(video_dir.into())
         };
         Some(user_dirs)
     } else {
@@ -63,36 +65,9 @@ pub fn user_dirs() -> Option<UserDirs> {
 }
 
 pub fn project_dirs_from_path(project_path: PathBuf) -> Option<ProjectDirs> {
-    if let Some(home_dir)    = dirs_sys::home_dir() {
-        let cache_dir        = home_dir.join("Library/Caches").join(&project_path);
-        let config_dir       = home_dir.join("Library/Application Support").join(&project_path);
-        let config_local_dir = config_dir.clone();
-        let data_dir         = config_dir.clone();
-        let data_local_dir   = config_dir.clone();
-        let preference_dir   = home_dir.join("Library/Preferences").join(&project_path);
-
-        let project_dirs = ProjectDirs {
-            project_path:     project_path,
-            cache_dir:        cache_dir,
-            config_dir:       config_dir,
-            config_local_dir: config_local_dir,
-            data_dir:         data_dir,
-            data_local_dir:   data_local_dir,
-            preference_dir:   preference_dir,
-            runtime_dir:      None,
-            state_dir:        None
-        };
-        Some(project_dirs)
-    } else {
-        None
-    }
+    base_dirs().map(|base_dirs| Platform::MacOs.project_dirs_from_base(&base_dirs, project_path))
 }
 
-pub fn project_dirs_from(qualifier: &str, organization: &str, application: &str) -> Option<ProjectDirs> {
-    // we should replace more characters, according to RFC1034 identifier rules
-    let organization = organization.replace(" ", "-");
-    let application  = application.replace(" ", "-");
-    let mut parts    = vec![qualifier, &organization, &application]; parts.retain(|e| !e.is_empty());
-    let bundle_id    = parts.join(".");
-    ProjectDirs::from_path(PathBuf::from(bundle_id))
+pub fn project_path_from(qualifier: &str, organization: &str, application: &str) -> PathBuf {
+    Platform::MacOs.project_path(qualifier, organization, application)
 }
@@ -0,0 +1,402 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use app_id::is_reserved_on_windows;
+use app_id::trim_and_lowercase_then_replace_spaces;
+use app_id::trim_then_replace_spaces;
+use Platform;
+use ProjectDirs;
+
+impl ProjectDirs {
+    /// Returns a `ProjectDirs` struct for the profile `name` of the project, e. g. `"work"` or `"personal"`,
+    /// whose directories are nested in a `profiles/<name>` subdirectory of the project path.
+    ///
+    /// The name is normalized according to the rules for project paths of the platform whose layout this struct has:
+    /// it is lowercased with whitespace removed on Linux and whitespace replaced by `-` on macOS.
+    /// On all platforms, characters which are not allowed in file names on Windows are replaced by `_`,
+    /// trailing dots and spaces are removed, and names reserved for devices on Windows, e. g. `CON` or `NUL.txt`, are prefixed by `_`.
+    /// Returns `None` if the normalized name is empty.
+    ///
+    /// |Platform | Example                                                                     |
+    /// | ------- | --------------------------------------------------------------------------- |
+    /// | Linux   | /home/alice/.config/barapp/profiles/work                                    |
+    /// | macOS   | /Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App/profiles/work |
+    /// | Windows | C:\Users\Alice\AppData\Roaming\Foo Corp\Bar App\profiles\work\config        |
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::ProjectDirs;
+    /// if let Some(proj_dirs) = ProjectDirs::from("com", "Foo Corp", "Bar App") {
+    ///     let work = proj_dirs.profile("work").unwrap();
+    ///     assert!(work.project_path().ends_with("work"));
+    /// }
+    /// ```
+    pub fn profile(&self, name: &str) -> Option<ProjectDirs> {
+        component_name(name, self.platform()).map(|name| self.nest(&Path::new("profiles").join(name)))
+    }
+    /// Returns a `ProjectDirs` struct for the component `name` of the project, e. g. a plugin or a helper tool,
+    /// whose directories are nested one level deeper, in a `<name>` subdirectory of the project path.
+    ///
+    /// The name is normalized like in [`ProjectDirs::profile`]. Returns `None` if the normalized name is empty.
+    /// The names `profiles` and `versions`, ignoring case, are prefixed by `_`, as these subdirectories hold
+    /// the directories returned by [`ProjectDirs::profile`] and [`ProjectDirs::versioned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::ProjectDirs;
+    /// if let Some(proj_dirs) = ProjectDirs::from("com", "Foo Corp", "Bar App") {
+    ///     let plugin = proj_dirs.child("Spell Checker").unwrap();
+    ///     plugin.config_dir();
+    ///     // Linux:   /home/alice/.config/barapp/spellchecker
+    ///     // Windows: C:\Users\Alice\AppData\Roaming\Foo Corp\Bar App\Spell Checker\config
+    ///     // macOS:   /Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App/Spell-Checker
+    /// }
+    /// ```
+    pub fn child(&self, name: &str) -> Option<ProjectDirs> {
+        component_name(name, self.platform()).map(|name| {
+            let name = if is_nesting_dir(&name) { format!("_{}", name) } else { name };
+            self.nest(Path::new(&name))
+        })
+    }
+    /// Returns the names of the profiles of the project which exist on disk, i. e. which have a config, data or cache directory,
+    /// in alphabetical order.
+    ///
+    /// Names which are not valid Unicode are skipped.
+    pub fn list_profiles(&self) -> Vec<String> {
+        let mut names = BTreeSet::new();
+        for dir in &[self.config_dir(), self.data_dir(), self.cache_dir()] {
+            let entries = match fs::read_dir(self.nest_base(dir).0.join("profiles")) {
+                Ok(entries) => entries,
+                Err(_)      => continue
+            };
+            for entry in entries.filter_map(Result::ok) {
+                if entry.file_type().map_or(false, |file_type| file_type.is_dir()) {
+                    names.extend(entry.file_name().into_string().ok());
+                }
+            }
+        }
+        names.into_iter().collect()
+    }
+    /// Returns a `ProjectDirs` struct for the version `version` of the project, e. g. `"2"` for a major version with an
+    /// incompatible on-disk format, whose directories are nested in a `versions/<version>` subdirectory of the project path.
+    ///
+    /// The version is normalized like the name in [`ProjectDirs::profile`]. Returns `None` if the normalized version is empty.
+    /// Files written by older versions can be located with [`ProjectDirs::previous_versions`] and [`ProjectDirs::find_in_previous_versions`].
+    ///
+    /// |Platform | Example                                                                  |
+    /// | ------- | ------------------------------------------------------------------------ |
+    /// | Linux   | /home/alice/.local/share/barapp/versions/2                               |
+    /// | macOS   | /Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App/versions/2 |
+    /// | Windows | C:\Users\Alice\AppData\Roaming\Foo Corp\Bar App\versions\2\data          |
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::ProjectDirs;
+    /// if let Some(proj_dirs) = ProjectDirs::from("com", "Foo Corp", "Bar App") {
+    ///     let v2 = proj_dirs.versioned("2").unwrap();
+    ///     if !v2.data_dir().join("library.db").exists() {
+    ///         if let Some(old) = v2.find_in_previous_versions("library.db") {
+    ///             eprintln!("migrating {}", old.display());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn versioned(&self, version: &str) -> Option<ProjectDirs> {
+        component_name(version, self.platform()).map(|version| self.nest(&Path::new("versions").join(version)))
+    }
+    /// Returns the versions of the project older than this version which exist on disk, i. e. which have a config or data directory,
+    /// newest first.
+    ///
+    /// Versions are compared by their `.`-separated parts, numerically if both parts are numbers, so that `"10"` is newer than `"9"`.
+    /// Names which are not valid Unicode are skipped. Returns an empty list if this struct was not returned by [`ProjectDirs::versioned`].
+    pub fn previous_versions(&self) -> Vec<String> {
+        let current = match self.version() {
+            Some(current) => current,
+            None          => return Vec::new()
+        };
+        let mut versions = BTreeSet::new();
+        for dir in &[self.config_dir(), self.data_dir()] {
+            let entries = match self.nest_base(dir).0.parent().map(fs::read_dir) {
+                Some(Ok(entries)) => entries,
+                _                 => continue
+            };
+            for entry in entries.filter_map(Result::ok) {
+                if entry.file_type().map_or(false, |file_type| file_type.is_dir()) {
+                    versions.extend(entry.file_name().into_string().ok().filter(|version| compare_versions(version, current) == Ordering::Less));
+                }
+            }
+        }
+        let mut versions: Vec<String> = versions.into_iter().collect();
+        versions.sort_by(|a, b| compare_versions(b, a));
+        versions
+    }
+    /// Returns the path of the file `file` written by an older version of the project, or `None` if it does not exist.
+    ///
+    /// The data and config directories of the versions returned by [`ProjectDirs::previous_versions`] are searched, newest first,
+    /// followed by the data and config directories of the project itself, where files were written before versioned directories were used.
+    pub fn find_in_previous_versions<P: AsRef<Path>>(&self, file: P) -> Option<PathBuf> {
+        self.version()?;
+        let file     = file.as_ref();
+        let versions = self.previous_versions();
+        versions.iter().map(|version| Some(version.as_str())).chain(Some(None))
+            .flat_map(|version| vec![self.version_dir(self.data_dir(), version), self.version_dir(self.config_dir(), version)])
+            .map(|dir| dir.join(file))
+            .find(|path| path.exists())
+    }
+    // Returns the version of this struct, if it was returned by `ProjectDirs::versioned`.
+    fn version(&self) -> Option<&str> {
+        match self.project_path.parent() {
+            Some(parent) if parent.file_name().map_or(false, |name| name == "versions") => self.project_path.file_name().and_then(|name| name.to_str()),
+            _                                                                          => None
+        }
+    }
+    // Returns the directory corresponding to `dir` for another version, or for the unversioned project if `version` is `None`.
+    fn version_dir(&self, dir: &Path, version: Option<&str>) -> PathBuf {
+        let (base, leaf) = self.nest_base(dir);
+        let versions     = base.parent().unwrap_or(base);
+        let base         = match version {
+            Some(version) => versions.join(version),
+            None          => versions.parent().unwrap_or(versions).to_path_buf()
+        };
+        match leaf {
+            Some(leaf) => base.join(leaf),
+            None       => base
+        }
+    }
+    // Returns a copy of this struct where `path` is appended to the project path, and inserted into each directory
+    // after the project path, so that the result matches `ProjectDirs::from_path` with the extended project path.
+    pub(crate) fn nest(&self, path: &Path) -> ProjectDirs {
+        let nest = |dir: &Path| -> Arc<Path> {
+            match self.nest_base(dir) {
+                (base, Some(leaf)) => base.join(path).join(leaf).into(),
+                (base, None)       => base.join(path).into()
+            }
+        };
+        ProjectDirs {
+            project_path:     self.project_path.join(path).into(),
+            cache_dir:        nest(&self.cache_dir),
+            config_dir:       nest(&self.config_dir),
+            config_local_dir: nest(&self.config_local_dir),
+            data_dir:         nest(&self.data_dir),
+            data_local_dir:   nest(&self.data_local_dir),
+            preference_dir:   nest(&self.preference_dir),
+            runtime_dir:      self.runtime_dir.as_ref().map(|dir| nest(dir)),
+            state_dir:        self.state_dir.as_ref().map(|dir| nest(dir))
+        }
+    }
+    // Splits `dir` into the part ending with the project path and the remaining component, e. g. `config` on Windows.
+    // Whether the layout appends such a component is decided for the whole struct, not per directory, as a project path
+    // may itself end with `config` or `data`, e. g. `config\config` for the application `config` on Windows.
+    fn nest_base<'a>(&self, dir: &'a Path) -> (&'a Path, Option<&'a Path>) {
+        if self.uses_leaves() {
+            if let (Some(parent), Some(leaf)) = (dir.parent(), dir.file_name()) {
+                if parent.ends_with(&self.project_path) {
+                    return (parent, Some(Path::new(leaf)));
+                }
+            }
+        }
+        (dir, None)
+    }
+    // Returns whether the directories have the Windows layout, where `config` or `data` follows the project path.
+    // The config and data directories are checked, as the cache and state directories may have been moved, e. g. by `host_local`.
+    fn uses_leaves(&self) -> bool {
+        [(&self.config_dir, "config"), (&self.config_local_dir, "config"), (&self.data_dir, "data"), (&self.data_local_dir, "data")].iter()
+            .all(|&(dir, leaf)| dir.file_name().map_or(false, |name| name == leaf) && dir.parent().map_or(false, |parent| parent.ends_with(&self.project_path)))
+    }
+    // Returns the platform whose layout the directories have, which may differ from `Platform::current`
+    // for structs returned by `Platform::project`. Only macOS has a preference directory separate from the config directory.
+    fn platform(&self) -> Platform {
+        if self.uses_leaves() {
+            Platform::Windows
+        } else if self.preference_dir != self.config_dir {
+            Platform::MacOs
+        } else {
+            Platform::Linux
+        }
+    }
+}
+
+// Normalizes a name like the application name in the project path of `platform`, and makes it a valid file name on all platforms,
+// so that directories synchronized between machines keep their names.
+pub(crate) fn component_name(name: &str, platform: Platform) -> Option<String> {
+    let name = match platform {
+        Platform::Linux   => trim_and_lowercase_then_replace_spaces(name, ""),
+        Platform::MacOs   => trim_then_replace_spaces(name, "-"),
+        Platform::Windows => name.trim().to_string()
+    };
+    let name: String = name.chars().map(|c| match c {
+        '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+        c if c.is_control()                                  => '_',
+        c                                                    => c
+    }).collect();
+    // Windows removes trailing dots and spaces from file names, which would turn e. g. `work.` into `work`.
+    let name = name.trim_end_matches(['.', ' ']);
+    if name.is_empty() {
+        None
+    } else if is_reserved_on_windows(name) {
+        Some(format!("_{}", name))
+    } else {
+        Some(name.to_string())
+    }
+}
+
+// Returns whether `name` is the subdirectory holding profiles or versions, compared like on the case-insensitive
+// filesystems of Windows and macOS.
+fn is_nesting_dir(name: &str) -> bool {
+    name.eq_ignore_ascii_case("profiles") || name.eq_ignore_ascii_case("versions")
+}
+
+// Compares versions by their `.`-separated parts, numerically if both parts are numbers.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split('.');
+    let mut b_parts = b.split('.');
+    loop {
+        let ordering = match (a_parts.next(), b_parts.next()) {
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                _              => a.cmp(b)
+            },
+            (Some(_), None)    => Ordering::Greater,
+            (None, Some(_))    => Ordering::Less,
+            (None, None)       => return Ordering::Equal
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::compare_versions;
+    use super::component_name;
+    use Platform;
+
+    #[test]
+    fn test_component_name() {
+        assert_eq!(component_name(" Work Stuff ", Platform::Linux),   Some("workstuff".to_string()));
+        assert_eq!(component_name(" Work Stuff ", Platform::MacOs),   Some("Work-Stuff".to_string()));
+        assert_eq!(component_name(" Work Stuff ", Platform::Windows), Some("Work Stuff".to_string()));
+        assert_eq!(component_name("a/b", Platform::Linux),            Some("a_b".to_string()));
+        assert_eq!(component_name("..", Platform::Windows),           None);
+        assert_eq!(component_name("   ", Platform::Linux),            None);
+        assert_eq!(component_name("work. .", Platform::Windows),      Some("work".to_string()));
+        assert_eq!(component_name("con", Platform::Windows),          Some("_con".to_string()));
+        assert_eq!(component_name("NUL.txt", Platform::MacOs),        Some("_NUL.txt".to_string()));
+        assert_eq!(component_name("COM1", Platform::Linux),           Some("_com1".to_string()));
+        assert_eq!(component_name("COM10", Platform::Windows),        Some("COM10".to_string()));
+    }
+
+    #[test]
+    fn test_child() {
+        if let Some(proj_dirs) = ::ProjectDirs::from("com", "Foo Corp", "Bar App") {
+            let plugin = proj_dirs.child("spell").unwrap();
+            assert_eq!(plugin.project_path(), proj_dirs.project_path().join("spell"));
+            assert_eq!(plugin.child("dict").unwrap().project_path(), proj_dirs.project_path().join("spell").join("dict"));
+            assert!(plugin.config_dir().starts_with(proj_dirs.config_dir().parent().unwrap()));
+            assert_eq!(proj_dirs.child(".."), None);
+        }
+    }
+
+    #[test]
+    fn test_child_reserved_names() {
+        let base_dirs = Platform::Windows.base_dirs(Path::new("/Users/Alice"));
+        let proj_dirs = Platform::Windows.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        let versions  = proj_dirs.child("versions").unwrap();
+        assert_eq!(versions.project_path(), Path::new("Foo Corp/Bar App/_versions"));
+        assert_eq!(versions.child("3").unwrap().version(), None);
+        assert_eq!(proj_dirs.child("Profiles").unwrap().project_path(), Path::new("Foo Corp/Bar App/_Profiles"));
+        assert_eq!(proj_dirs.versioned("3").unwrap().version(), Some("3"));
+    }
+
+    #[test]
+    fn test_nest() {
+        let base_dirs = Platform::Linux.base_dirs(Path::new("/home/alice"));
+        let proj_dirs = Platform::Linux.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        let work      = proj_dirs.nest(Path::new("profiles/work"));
+        assert_eq!(work.project_path(), Path::new("barapp/profiles/work"));
+        assert_eq!(work.cache_dir(),    Path::new("/home/alice/.cache/barapp/profiles/work"));
+        assert_eq!(work, Platform::Linux.project_dirs_from_base(&base_dirs, work.project_path().to_path_buf()));
+
+        let base_dirs = Platform::Windows.base_dirs(Path::new("/Users/Alice"));
+        let proj_dirs = Platform::Windows.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        let work      = proj_dirs.nest(Path::new("profiles/work"));
+        assert_eq!(work.config_dir(), proj_dirs.config_dir().parent().unwrap().join("profiles/work").join("config"));
+        assert_eq!(work, Platform::Windows.project_dirs_from_base(&base_dirs, work.project_path().to_path_buf()));
+
+        // The application name is the same as the component appended by the Windows layout.
+        let proj_dirs = Platform::Windows.project(&base_dirs, "", "", "config").unwrap();
+        let work      = proj_dirs.profile("Work").unwrap();
+        assert_eq!(work, Platform::Windows.project_dirs_from_base(&base_dirs, Path::new("config/profiles/Work").to_path_buf()));
+        let v2        = proj_dirs.versioned("2").unwrap();
+        assert_eq!(v2.version_dir(v2.config_dir(), Some("1")), proj_dirs.versioned("1").unwrap().config_dir());
+        assert_eq!(v2.version_dir(v2.data_dir(), None),        proj_dirs.data_dir());
+
+        // The name is normalized according to the layout of the struct, not the current platform.
+        let base_dirs = Platform::Linux.base_dirs(Path::new("/home/alice"));
+        let proj_dirs = Platform::Linux.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        assert_eq!(proj_dirs.profile("Work Stuff").unwrap().project_path(), Path::new("barapp/profiles/workstuff"));
+        let base_dirs = Platform::MacOs.base_dirs(Path::new("/Users/Alice"));
+        let proj_dirs = Platform::MacOs.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        assert_eq!(proj_dirs.profile("Work Stuff").unwrap().project_path(), Path::new("com.Foo-Corp.Bar-App/profiles/Work-Stuff"));
+    }
+
+    #[test]
+    fn test_list_profiles() {
+        use std::fs;
+
+        use test_util::TempDir;
+
+        let temp_dir  = TempDir::new("profiles");
+        let base_dirs = Platform::current().base_dirs(&temp_dir);
+        let proj_dirs = Platform::current().project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        fs::create_dir_all(proj_dirs.profile("work").unwrap().config_dir()).unwrap();
+        fs::create_dir_all(proj_dirs.profile("home").unwrap().cache_dir()).unwrap();
+        assert_eq!(proj_dirs.list_profiles(), vec!["home".to_string(), "work".to_string()]);
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        use std::cmp::Ordering;
+
+        assert_eq!(compare_versions("10", "9"),    Ordering::Greater);
+        assert_eq!(compare_versions("1.5", "2"),   Ordering::Less);
+        assert_eq!(compare_versions("2", "2.0"),   Ordering::Less);
+        assert_eq!(compare_versions("beta", "2"),  Ordering::Greater);
+        assert_eq!(compare_versions("1.2", "1.2"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_previous_versions() {
+        use std::fs;
+
+        use test_util::TempDir;
+
+        let temp_dir  = TempDir::new("versions");
+        let base_dirs = Platform::Linux.base_dirs(&temp_dir);
+        let proj_dirs = Platform::Linux.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        let v2        = proj_dirs.nest(Path::new("versions/2"));
+        for version in &["1", "1.5", "10"] {
+            fs::create_dir_all(proj_dirs.nest(&Path::new("versions").join(version)).data_dir()).unwrap();
+        }
+        fs::create_dir_all(proj_dirs.config_dir()).unwrap();
+        fs::write(proj_dirs.config_dir().join("a.toml"), "").unwrap();
+        fs::write(proj_dirs.nest(Path::new("versions/1")).data_dir().join("a.toml"), "").unwrap();
+        fs::write(proj_dirs.config_dir().join("b.toml"), "").unwrap();
+
+        assert_eq!(v2.previous_versions(), vec!["1.5".to_string(), "1".to_string()]);
+        assert_eq!(v2.find_in_previous_versions("a.toml"), Some(proj_dirs.data_dir().join("versions/1/a.toml")));
+        assert_eq!(v2.find_in_previous_versions("b.toml"), Some(proj_dirs.config_dir().join("b.toml")));
+        assert_eq!(v2.find_in_previous_versions("c.toml"), None);
+        assert!(proj_dirs.previous_versions().is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use app_id::bundle_id;
+use app_id::trim_and_lowercase_then_replace_spaces;
+use validate_project_path;
+use BaseDirs;
+use Platform;
+
+/// `OrgDirs` provides paths of directories shared by all applications of an organization,
+/// e. g. for license files, credentials caches or shared data.
+///
+/// The directories are located where [`ProjectDirs`](struct.ProjectDirs.html) places the directories of an application,
+/// with the organization path in place of the project path. The organization path ends with a `shared` component,
+/// as its first component alone is also the project path of some applications:
+///
+/// |Platform | Organization path   | Project path of an application | Application with the same first component  |
+/// | ------- | ------------------- | ------------------------------ | ------------------------------------------ |
+/// | Linux   | foocorp/shared      | barapp                         | `ProjectDirs::from("", "", "Foo Corp")`    |
+/// | macOS   | com.Foo-Corp/shared | com.Foo-Corp.Bar-App           | `ProjectDirs::from("com", "", "Foo Corp")` |
+/// | Windows | Foo Corp\shared     | Foo Corp\Bar App               | `ProjectDirs::from("", "", "Foo Corp")`    |
+///
+/// On Windows, the directories of the applications are nested inside the organization directory.
+/// On Linux, project paths do not include the organization, and on macOS, bundle identifiers are not nested,
+/// so the organization directory is a sibling of the application directories.
+///
+/// The directories still collide with those of an application named `shared` of the organization on Windows,
+/// and with the `shared` subdirectory of an application named like the organization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OrgDirs {
+    org_path:   Arc<Path>,
+    cache_dir:  Arc<Path>,
+    config_dir: Arc<Path>,
+    data_dir:   Arc<Path>
+}
+
+impl OrgDirs {
+    /// Creates an `OrgDirs` struct from values describing the organization.
+    ///
+    /// The returned value is either
+    /// - `Some`, containing organization directory paths based on the state of the system's paths at the time `from()` was invoked, or
+    /// - `None`, if `organization` is empty, consists only of whitespace or results in an invalid path, e. g. `".."` on Windows,
+    ///   or if no valid home directory path could be retrieved from the operating system.
+    ///
+    /// The parameters have the same meaning as in [`ProjectDirs::from`](struct.ProjectDirs.html#method.from).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::OrgDirs;
+    /// if let Some(org_dirs) = OrgDirs::from("com", "Foo Corp") {
+    ///     org_dirs.config_dir();
+    ///     // Linux:   /home/alice/.config/foocorp/shared
+    ///     // Windows: C:\Users\Alice\AppData\Roaming\Foo Corp\shared\config
+    ///     // macOS:   /Users/Alice/Library/Application Support/com.Foo-Corp/shared
+    /// }
+    /// ```
+    pub fn from(qualifier: &str, organization: &str) -> Option<OrgDirs> {
+        BaseDirs::new().and_then(|base_dirs| Platform::current().org(&base_dirs, qualifier, organization))
+    }
+    /// Returns the organization path fragment used to compute the organization's cache/config/data directories.
+    pub fn org_path(&self) -> &Path {
+        &self.org_path
+    }
+    /// Returns the path to the organization's cache directory.
+    ///
+    /// |Platform | Example                                             |
+    /// | ------- | --------------------------------------------------- |
+    /// | Linux   | /home/alice/.cache/foocorp/shared                   |
+    /// | macOS   | /Users/Alice/Library/Caches/com.Foo-Corp/shared     |
+    /// | Windows | C:\Users\Alice\AppData\Local\Foo Corp\shared\cache  |
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+    /// Returns the path to the organization's config directory.
+    ///
+    /// |Platform | Example                                                      |
+    /// | ------- | ------------------------------------------------------------ |
+    /// | Linux   | /home/alice/.config/foocorp/shared                           |
+    /// | macOS   | /Users/Alice/Library/Application Support/com.Foo-Corp/shared |
+    /// | Windows | C:\Users\Alice\AppData\Roaming\Foo Corp\shared\config        |
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+    /// Returns the path to the organization's data directory.
+    ///
+    /// |Platform | Example                                                      |
+    /// | ------- | ------------------------------------------------------------ |
+    /// | Linux   | /home/alice/.local/share/foocorp/shared                      |
+    /// | macOS   | /Users/Alice/Library/Application Support/com.Foo-Corp/shared |
+    /// | Windows | C:\Users\Alice\AppData\Roaming\Foo Corp\shared\data          |
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+}
+
+impl Platform {
+    /// Creates an `OrgDirs` struct from values describing the organization, deriving its paths from `base_dirs`
+    /// according to the conventions of this platform.
+    ///
+    /// For [`Platform::current`] and the base directories returned by [`BaseDirs::new`], this returns the same value as [`OrgDirs::from`].
+    pub fn org(self, base_dirs: &BaseDirs, qualifier: &str, organization: &str) -> Option<OrgDirs> {
+        if organization.trim().is_empty() {
+            return None;
+        }
+        let org_path = match self {
+            Platform::Linux   => PathBuf::from(trim_and_lowercase_then_replace_spaces(organization, "")),
+            Platform::MacOs   => PathBuf::from(bundle_id(qualifier, organization, "")),
+            Platform::Windows => PathBuf::from(organization)
+        };
+        if validate_project_path(&org_path).is_err() {
+            return None;
+        }
+        let proj_dirs = self.project_dirs_from_base(base_dirs, org_path.join("shared"));
+        Some(OrgDirs {
+            org_path:   proj_dirs.project_path,
+            cache_dir:  proj_dirs.cache_dir,
+            config_dir: proj_dirs.config_dir,
+            data_dir:   proj_dirs.data_dir
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use Platform;
+
+    #[test]
+    fn test_org() {
+        let base_dirs = Platform::Linux.base_dirs(Path::new("/home/alice"));
+        let org_dirs  = Platform::Linux.org(&base_dirs, "com", " Foo Corp ").unwrap();
+        assert_eq!(org_dirs.config_dir(), Path::new("/home/alice/.config/foocorp/shared"));
+        assert_eq!(org_dirs.data_dir(),   Path::new("/home/alice/.local/share/foocorp/shared"));
+        let proj_dirs = Platform::Linux.project(&base_dirs, "", "", "Foo Corp").unwrap();
+        assert_ne!(org_dirs.config_dir(), proj_dirs.config_dir());
+
+        let base_dirs = Platform::MacOs.base_dirs(Path::new("/Users/Alice"));
+        let org_dirs  = Platform::MacOs.org(&base_dirs, "com", "Foo Corp").unwrap();
+        assert_eq!(org_dirs.cache_dir(), Path::new("/Users/Alice/Library/Caches/com.Foo-Corp/shared"));
+
+        let base_dirs = Platform::Windows.base_dirs(Path::new("/Users/Alice"));
+        let org_dirs  = Platform::Windows.org(&base_dirs, "com", "Foo Corp").unwrap();
+        let proj_dirs = Platform::Windows.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        assert!(proj_dirs.config_dir().starts_with(org_dirs.config_dir().parent().unwrap().parent().unwrap()));
+        assert_eq!(org_dirs.config_dir(), Path::new("/Users/Alice/AppData/Roaming/Foo Corp/shared/config"));
+        let proj_dirs = Platform::Windows.project(&base_dirs, "com", "Foo Corp", "config").unwrap();
+        assert_ne!(org_dirs.config_dir(), proj_dirs.config_dir());
+
+        assert_eq!(Platform::Linux.org(&base_dirs, "com", "  "),   None);
+        assert_eq!(Platform::Windows.org(&base_dirs, "com", ".."), None);
+    }
+}
@@ -0,0 +1,59 @@
+// Lookups in the user database, which is only available on Unix.
+
+#[cfg(unix)]
+extern crate libc;
+
+use std::path::PathBuf;
+
+// Returns the home directory of the user named `name`, or `None` if there is no such user or the home directory is empty.
+#[cfg(unix)]
+pub(crate) fn home_dir_of(name: &str) -> Option<PathBuf> {
+    use std::ffi::CString;
+
+    let name = CString::new(name).ok()?;
+    lookup(|passwd, buf, len, result| unsafe { libc::getpwnam_r(name.as_ptr(), passwd, buf, len, result) })
+}
+
+// Returns the home directory of the user with the id `uid`, or `None` if there is no such user or the home directory is empty.
+#[cfg(unix)]
+pub(crate) fn home_dir_of_uid(uid: u32) -> Option<PathBuf> {
+    lookup(|passwd, buf, len, result| unsafe { libc::getpwuid_r(uid, passwd, buf, len, result) })
+}
+
+#[cfg(unix)]
+fn lookup<F>(get: F) -> Option<PathBuf>
+    where F: Fn(*mut libc::passwd, *mut libc::c_char, libc::size_t, *mut *mut libc::passwd) -> libc::c_int
+{
+    use std::ffi::CStr;
+    use std::ffi::OsStr;
+    use std::mem;
+    use std::os::unix::ffi::OsStrExt;
+    use std::ptr;
+
+    let mut buf = vec![0 as libc::c_char; 1024];
+    loop {
+        let mut passwd: libc::passwd = unsafe { mem::zeroed() };
+        let mut result = ptr::null_mut();
+        let code = get(&mut passwd, buf.as_mut_ptr(), buf.len(), &mut result);
+        if code == libc::ERANGE && buf.len() < 1 << 20 {
+            let len = buf.len() * 2;
+            buf.resize(len, 0);
+            continue;
+        }
+        if code != 0 || result.is_null() || passwd.pw_dir.is_null() {
+            return None;
+        }
+        let home_dir = unsafe { CStr::from_ptr(passwd.pw_dir) }.to_bytes();
+        return if home_dir.is_empty() { None } else { Some(PathBuf::from(OsStr::from_bytes(home_dir))) };
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn home_dir_of(_name: &str) -> Option<PathBuf> {
+    None
+}
+
+#[cfg(not(unix))]
+pub(crate) fn home_dir_of_uid(_uid: u32) -> Option<PathBuf> {
+    None
+}
@@ -0,0 +1,281 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use app_id::bundle_id;
+use app_id::trim_and_lowercase_then_replace_spaces;
+use validate_project_path;
+use BaseDirs;
+use ProjectDirs;
+use ProjectDirsError;
+use UserDirs;
+
+/// `Platform` identifies the conventions used to lay out the directories of a home directory.
+///
+/// Besides [`Platform::current`], which the constructors of this library follow, a `Platform` can be used to
+/// compute the layout another operating system would use, e. g. for tools that generate files for other platforms.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use directories::Platform;
+///
+/// let base_dirs = Platform::MacOs.base_dirs(Path::new("/Users/Alice"));
+/// assert_eq!(base_dirs.config_dir(), Path::new("/Users/Alice/Library/Application Support"));
+///
+/// let proj_dirs = Platform::MacOs.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+/// assert_eq!(proj_dirs.config_dir(), Path::new("/Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    /// The XDG base directory and user directory specifications, also used on the BSDs and other Unix systems.
+    Linux,
+    /// The Standard Directories of macOS.
+    MacOs,
+    /// The Known Folders of Windows.
+    Windows
+}
+
+impl Platform {
+    /// Returns the platform the library is running on.
+    pub fn current() -> Platform {
+        if cfg!(target_os = "windows") {
+            Platform::Windows
+        } else if cfg!(any(target_os = "macos", target_os = "ios")) {
+            Platform::MacOs
+        } else {
+            Platform::Linux
+        }
+    }
+    /// Returns the name of the platform in lowercase, e. g. `"macos"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Platform::Linux   => "linux",
+            Platform::MacOs   => "macos",
+            Platform::Windows => "windows"
+        }
+    }
+    /// Creates a `BaseDirs` struct holding the default paths of this platform inside `home_dir`.
+    ///
+    /// Neither environment variables nor the operating system are consulted, so the returned paths are those
+    /// used when nothing has been customized: on Linux, none of the `$XDG_*` variables is set, which means that
+    /// no runtime directory is available; on Windows, the Known Folders are in their default locations.
+    pub fn base_dirs(self, home_dir: &Path) -> BaseDirs {
+        match self {
+            Platform::Linux => {
+                let config_dir = home_dir.join(".config");
+                let data_dir   = home_dir.join(".local").join("share");
+                BaseDirs {
+                    home_dir:         home_dir.into(),
+                    cache_dir:        home_dir.join(".cache").into(),
+                    config_dir:       config_dir.clone().into(),
+                    config_local_dir: config_dir.clone().into(),
+                    data_dir:         data_dir.clone().into(),
+                    data_local_dir:   data_dir.into(),
+                    executable_dir:   Some(home_dir.join(".local").join("bin").into()),
+                    preference_dir:   config_dir.into(),
+                    runtime_dir:      None,
+                    state_dir:        Some(home_dir.join(".local").join("state").into())
+                }
+            },
+            Platform::MacOs => {
+                let library_dir = home_dir.join("Library");
+                let config_dir  = library_dir.join("Application Support");
+                BaseDirs {
+                    home_dir:         home_dir.into(),
+                    cache_dir:        library_dir.join("Caches").into(),
+                    config_dir:       config_dir.clone().into(),
+                    config_local_dir: config_dir.clone().into(),
+                    data_dir:         config_dir.clone().into(),
+                    data_local_dir:   config_dir.into(),
+                    executable_dir:   None,
+                    preference_dir:   library_dir.join("Preferences").into(),
+                    runtime_dir:      None,
+                    state_dir:        None
+                }
+            },
+            Platform::Windows => {
+                let app_data_roaming = home_dir.join("AppData").join("Roaming");
+                let app_data_local   = home_dir.join("AppData").join("Local");
+                BaseDirs {
+                    home_dir:         home_dir.into(),
+                    cache_dir:        app_data_local.clone().into(),
+                    config_dir:       app_data_roaming.clone().into(),
+                    config_local_dir: app_data_local.clone().into(),
+                    data_dir:         app_data_roaming.clone().into(),
+                    data_local_dir:   app_data_local.into(),
+                    executable_dir:   None,
+                    preference_dir:   app_data_roaming.into(),
+                    runtime_dir:      None,
+                    state_dir:        None
+                }
+            }
+        }
+    }
+    /// Creates a `UserDirs` struct holding the default paths of this platform inside `home_dir`.
+    ///
+    /// Neither environment variables nor the operating system are consulted: on Linux, the returned paths are the
+    /// defaults written to `user-dirs.dirs` by `xdg-user-dirs-update` for an English locale.
+    pub fn user_dirs(self, home_dir: &Path) -> UserDirs {
+        let path = |name: &str| -> Option<Arc<Path>> { Some(home_dir.join(name).into()) };
+        match self {
+            Platform::Linux => UserDirs {
+                home_dir:     home_dir.into(),
+                audio_dir:    path("Music"),
+                desktop_dir:  path("Desktop"),
+                document_dir: path("Documents"),
+                download_dir: path("Downloads"),
+                font_dir:     Some(home_dir.join(".local").join("share").join("fonts").into()),
+                picture_dir:  path("Pictures"),
+                public_dir:   path("Public"),
+                template_dir: path("Templates"),
+                video_dir:    path("Videos")
+            },
+            Platform::MacOs => UserDirs {
+                home_dir:     home_dir.into(),
+                audio_dir:    path("Music"),
+                desktop_dir:  path("Desktop"),
+                document_dir: path("Documents"),
+                download_dir: path("Downloads"),
+                font_dir:     Some(home_dir.join("Library").join("Fonts").into()),
+                picture_dir:  path("Pictures"),
+                public_dir:   path("Public"),
+                template_dir: None,
+                video_dir:    path("Movies")
+            },
+            Platform::Windows => UserDirs {
+                home_dir:     home_dir.into(),
+                audio_dir:    path("Music"),
+                desktop_dir:  path("Desktop"),
+                document_dir: path("Documents"),
+                download_dir: path("Downloads"),
+                font_dir:     None,
+                picture_dir:  path("Pictures"),
+                public_dir:   home_dir.parent().map(|users_dir| users_dir.join("Public").into()),
+                template_dir: Some(home_dir.join("AppData").join("Roaming").join("Microsoft").join("Windows").join("Templates").into()),
+                video_dir:    path("Videos")
+            }
+        }
+    }
+    /// Creates a `ProjectDirs` struct from values describing the project, deriving its paths from `base_dirs`
+    /// according to the conventions of this platform.
+    ///
+    /// For [`Platform::current`], this returns the same value as [`BaseDirs::project`].
+    /// The parameters have the same meaning and are validated the same way as in [`ProjectDirs::try_from`].
+    pub fn project(self, base_dirs: &BaseDirs, qualifier: &str, organization: &str, application: &str) -> Result<ProjectDirs, ProjectDirsError> {
+        if application.trim().is_empty() {
+            return Err(ProjectDirsError::EmptyApplication);
+        }
+        let project_path = self.project_path(qualifier, organization, application);
+        validate_project_path(&project_path)?;
+        Ok(self.project_dirs_from_base(base_dirs, project_path))
+    }
+    pub(crate) fn project_path(self, qualifier: &str, organization: &str, application: &str) -> PathBuf {
+        match self {
+            Platform::Linux   => PathBuf::from(&trim_and_lowercase_then_replace_spaces(application, "")),
+            Platform::MacOs   => PathBuf::from(bundle_id(qualifier, organization, application)),
+            Platform::Windows => PathBuf::from_iter(&[organization, application])
+        }
+    }
+    pub(crate) fn project_dirs_from_base(self, base_dirs: &BaseDirs, project_path: PathBuf) -> ProjectDirs {
+        match self {
+            Platform::Linux => {
+                let config_dir = base_dirs.config_dir.join(&project_path);
+                let data_dir   = base_dirs.data_dir.join(&project_path);
+                ProjectDirs {
+                    cache_dir:        base_dirs.cache_dir.join(&project_path).into(),
+                    config_dir:       config_dir.clone().into(),
+                    config_local_dir: config_dir.clone().into(),
+                    data_dir:         data_dir.clone().into(),
+                    data_local_dir:   data_dir.into(),
+                    preference_dir:   config_dir.into(),
+                    runtime_dir:      base_dirs.runtime_dir.as_ref().map(|o| o.join(&project_path).into()),
+                    state_dir:        base_dirs.state_dir.as_ref().map(|o| o.join(&project_path).into()),
+                    project_path:     project_path.into()
+                }
+            },
+            Platform::MacOs => {
+                let config_dir = base_dirs.config_dir.join(&project_path);
+                ProjectDirs {
+                    cache_dir:        base_dirs.cache_dir.join(&project_path).into(),
+                    config_dir:       config_dir.clone().into(),
+                    config_local_dir: config_dir.clone().into(),
+                    data_dir:         config_dir.clone().into(),
+                    data_local_dir:   config_dir.into(),
+                    preference_dir:   base_dirs.preference_dir.join(&project_path).into(),
+                    runtime_dir:      None,
+                    state_dir:        None,
+                    project_path:     project_path.into()
+                }
+            },
+            Platform::Windows => windows_project_dirs(&base_dirs.data_local_dir, &base_dirs.data_dir, project_path)
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+pub(crate) fn windows_project_dirs(app_data_local: &Path, app_data_roaming: &Path, project_path: PathBuf) -> ProjectDirs {
+    let app_data_local   = app_data_local.join(&project_path);
+    let app_data_roaming = app_data_roaming.join(&project_path);
+    let cache_dir        = app_data_local.join("cache");
+    let data_local_dir   = app_data_local.join("data");
+    let config_dir       = app_data_roaming.join("config");
+    let config_local_dir = app_data_local.join("config");
+    let data_dir         = app_data_roaming.join("data");
+    let preference_dir   = config_dir.clone();
+
+    ProjectDirs {
+        project_path:     project_path.into(),
+        cache_dir:        cache_dir.into(),
+        config_dir:       config_dir.into(),
+        config_local_dir: config_local_dir.into(),
+        data_dir:         data_dir.into(),
+        data_local_dir:   data_local_dir.into(),
+        preference_dir:   preference_dir.into(),
+        runtime_dir:      None,
+        state_dir:        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use Platform;
+
+    #[test]
+    fn test_layouts() {
+        let home_dir = Path::new("/home/alice");
+
+        let base_dirs = Platform::Linux.base_dirs(home_dir);
+        assert_eq!(base_dirs.data_dir(), Path::new("/home/alice/.local/share"));
+        assert_eq!(base_dirs.runtime_dir(), None);
+        let proj_dirs = Platform::Linux.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        assert_eq!(proj_dirs.state_dir(), Some(Path::new("/home/alice/.local/state/barapp")));
+
+        let base_dirs = Platform::Windows.base_dirs(home_dir);
+        let proj_dirs = Platform::Windows.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        let expected: PathBuf = ["/home/alice", "AppData", "Local", "Foo Corp", "Bar App", "cache"].iter().collect();
+        assert_eq!(proj_dirs.cache_dir(), expected);
+        assert_eq!(Platform::Windows.user_dirs(home_dir).public_dir(), Some(Path::new("/home/Public")));
+
+        assert_eq!(Platform::MacOs.project(&base_dirs, "com", "Foo Corp", " "), Err(::ProjectDirsError::EmptyApplication));
+    }
+
+    #[test]
+    fn test_current() {
+        if let Some(base_dirs) = ::BaseDirs::new() {
+            let expected = base_dirs.project("com", "Foo Corp", "Bar App");
+            assert_eq!(expected, Platform::current().project(&base_dirs, "com", "Foo Corp", "Bar App"));
+        }
+    }
+}
@@ -0,0 +1,196 @@
+// Inspection of the filesystem a directory is located on, shared by the diagnostics and the placement of directories.
+
+#[cfg(unix)]
+extern crate libc;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// `FileSystem` describes the filesystem a directory is located on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileSystem {
+    /// The name of the filesystem type, e. g. `"ext4"`, `"tmpfs"` or `"nfs"`,
+    /// or its magic number in hexadecimal notation if it is not known to this library.
+    pub name: String,
+    /// The kind of storage the filesystem is backed by.
+    pub kind: FileSystemKind
+}
+
+/// `FileSystemKind` distinguishes filesystems by the kind of storage they are backed by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileSystemKind {
+    /// A filesystem on a local device.
+    Local,
+    /// A filesystem held in memory, e. g. `tmpfs`, whose contents are lost on reboot.
+    Memory,
+    /// A filesystem provided by another host, e. g. NFS or SMB.
+    Network,
+    /// A filesystem whose storage could not be determined,
+    /// e. g. FUSE, which may as well be backed by a local device as by another host (sshfs, rclone).
+    Unknown
+}
+
+impl fmt::Display for FileSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            FileSystemKind::Local   => f.write_str(&self.name),
+            FileSystemKind::Memory  => write!(f, "{} (in memory)", self.name),
+            FileSystemKind::Network => write!(f, "{} (network)", self.name),
+            FileSystemKind::Unknown => write!(f, "{} (unknown storage)", self.name)
+        }
+    }
+}
+
+// The space available to unprivileged users and the mount flags of a filesystem.
+pub(crate) struct Capacity {
+    pub(crate) available: u64,
+    pub(crate) read_only: bool
+}
+
+// Returns `path` if it exists, otherwise its nearest ancestor which exists.
+pub(crate) fn nearest_existing(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|ancestor| fs::symlink_metadata(ancestor).is_ok())
+}
+
+#[cfg(unix)]
+fn c_path(path: &Path) -> Option<::std::ffi::CString> {
+    use std::os::unix::ffi::OsStrExt;
+    ::std::ffi::CString::new(path.as_os_str().as_bytes()).ok()
+}
+
+// `access` checks the permissions of the real user, which differs from the effective user under setuid,
+// so `faccessat` with `AT_EACCESS` is used to check those of the user the process creates files as.
+#[cfg(unix)]
+pub(crate) fn is_writable(path: &Path) -> bool {
+    match c_path(path) {
+        Some(path) => unsafe { libc::faccessat(libc::AT_FDCWD, path.as_ptr(), libc::W_OK, libc::AT_EACCESS) == 0 },
+        None       => false
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_writable(path: &Path) -> bool {
+    fs::metadata(path).map(|metadata| !metadata.permissions().readonly()).unwrap_or(false)
+}
+
+// Creates a directory only accessible by the current user, or checks that the existing directory at `path` is one:
+// a directory, not a symbolic link, owned by the effective user and with mode 0700.
+// Paths in the shared temporary directory are predictable, so other users may have created them first.
+#[cfg(unix)]
+pub(crate) fn create_private_dir(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    use std::os::unix::fs::MetadataExt;
+
+    match fs::DirBuilder::new().mode(0o700).create(path) {
+        Err(ref error) if error.kind() == io::ErrorKind::AlreadyExists => {},
+        result                                                         => return result
+    }
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.file_type().is_dir() && Some(metadata.uid()) == current_uid() && metadata.mode() & 0o777 == 0o700 {
+        Ok(())
+    } else {
+        Err(io::Error::new(io::ErrorKind::PermissionDenied, "directory is not private to the current user"))
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn create_private_dir(path: &Path) -> io::Result<()> {
+    match fs::create_dir(path) {
+        Err(ref error) if error.kind() == io::ErrorKind::AlreadyExists => {},
+        result                                                         => return result
+    }
+    if fs::symlink_metadata(path)?.file_type().is_dir() {
+        Ok(())
+    } else {
+        Err(io::Error::new(io::ErrorKind::PermissionDenied, "path is not a directory"))
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn current_uid() -> Option<u32> {
+    Some(unsafe { libc::geteuid() })
+}
+
+#[cfg(not(unix))]
+pub(crate) fn current_uid() -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+pub(crate) fn capacity(path: &Path) -> Option<Capacity> {
+    let path = c_path(path)?;
+    let mut stat: libc::statvfs = unsafe { ::std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    let capacity = Capacity {
+        available: (stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64),
+        read_only: stat.f_flag & libc::ST_RDONLY != 0
+    };
+    Some(capacity)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn capacity(_path: &Path) -> Option<Capacity> {
+    None
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn file_system(path: &Path) -> Option<FileSystem> {
+    let path = c_path(path)?;
+    let mut stat: libc::statfs = unsafe { ::std::mem::zeroed() };
+    if unsafe { libc::statfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    // f_type is signed on some architectures, but the magic numbers are defined as 32-bit values.
+    let (name, kind) = match stat.f_type as u32 {
+        0x0102_1994 => ("tmpfs",    FileSystemKind::Memory),
+        0x8584_58f6 => ("ramfs",    FileSystemKind::Memory),
+        0x0000_6969 => ("nfs",      FileSystemKind::Network),
+        0xff53_4d42 => ("cifs",     FileSystemKind::Network),
+        0xfe53_4d42 => ("smb2",     FileSystemKind::Network),
+        0x0000_517b => ("smb",      FileSystemKind::Network),
+        0x0000_564c => ("ncp",      FileSystemKind::Network),
+        0x5346_414f => ("afs",      FileSystemKind::Network),
+        0x00c3_6400 => ("ceph",     FileSystemKind::Network),
+        0x0102_1997 => ("9p",       FileSystemKind::Network),
+        0x0000_ef53 => ("ext4",     FileSystemKind::Local),
+        0x9123_683e => ("btrfs",    FileSystemKind::Local),
+        0x5846_5342 => ("xfs",      FileSystemKind::Local),
+        0x2fc1_2fc1 => ("zfs",      FileSystemKind::Local),
+        0xf2f5_2010 => ("f2fs",     FileSystemKind::Local),
+        0x794c_7630 => ("overlay",  FileSystemKind::Local),
+        0x7371_7368 => ("squashfs", FileSystemKind::Local),
+        0x0000_4d44 => ("vfat",     FileSystemKind::Local),
+        0x5346_544e => ("ntfs",     FileSystemKind::Local),
+        0x6573_5546 => ("fuse",     FileSystemKind::Unknown),
+        magic       => return Some(FileSystem { name: format!("{:#x}", magic), kind: FileSystemKind::Unknown })
+    };
+    Some(FileSystem { name: name.to_string(), kind })
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+pub(crate) fn file_system(path: &Path) -> Option<FileSystem> {
+    use std::ffi::CStr;
+
+    let path = c_path(path)?;
+    let mut stat: libc::statfs = unsafe { ::std::mem::zeroed() };
+    if unsafe { libc::statfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    let name = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) }.to_string_lossy().into_owned();
+    let kind = match name.as_str() {
+        "tmpfs"                                               => FileSystemKind::Memory,
+        "nfs" | "smbfs" | "afpfs" | "webdav" | "cifs" | "ftp" => FileSystemKind::Network,
+        "apfs" | "hfs" | "ufs" | "zfs" | "msdos" | "exfat"    => FileSystemKind::Local,
+        _                                                     => FileSystemKind::Unknown
+    };
+    Some(FileSystem { name, kind })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd")))]
+pub(crate) fn file_system(_path: &Path) -> Option<FileSystem> {
+    None
+}
@@ -0,0 +1,203 @@
+use std::env;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use probe;
+use DirKind;
+use ProjectDirs;
+
+/// `RedirectTarget` selects where [`ProjectDirs::redirect_unwritable`] moves a directory which cannot be written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RedirectTarget {
+    /// A subdirectory of the runtime directory of the project, which is typically located in memory and removed on logout.
+    /// If there is no runtime directory, or it is not writable either, [`RedirectTarget::TempDir`] is used instead.
+    RuntimeDir,
+    /// A subdirectory of the temporary directory of the system, named after the project and the current user,
+    /// which is only accessible by the current user.
+    TempDir,
+    /// The given directory.
+    Dir(PathBuf)
+}
+
+/// `Redirection` records a directory which was moved by [`ProjectDirs::redirect_unwritable`] or [`ProjectDirs::host_local`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Redirection {
+    /// The kind of the directory.
+    pub kind: DirKind,
+    /// The original path of the directory.
+    pub original: PathBuf,
+    /// The path the directory was moved to.
+    pub redirected: PathBuf
+}
+
+impl ProjectDirs {
+    /// Checks whether the directories listed in `targets` are writable, and returns a copy of this struct where each
+    /// unwritable directory is replaced by its redirect target, together with the redirections which were applied.
+    ///
+    /// This handles home directories which are mounted read-only, e. g. with `ProtectHome=read-only` in systemd units,
+    /// on kiosk systems or on read-only network filesystems. Like in [`ProjectDirs::diagnose`], a directory which does not
+    /// exist is writable if it can be created in its nearest existing ancestor.
+    /// The redirect targets are not checked or created, except for the parent of the subdirectories used by
+    /// [`RedirectTarget::TempDir`], which is created only accessible by the current user. If that directory already exists
+    /// and is a symbolic link, is owned by another user or is accessible by other users, the runtime directory is used instead,
+    /// and if it is not available either, the directory is not redirected.
+    /// Files written to the redirect targets usually do not persist, so applications should warn about each returned [`Redirection`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use directories::{DirKind, ProjectDirs, RedirectTarget};
+    /// if let Some(proj_dirs) = ProjectDirs::from("com", "Foo Corp", "Bar App") {
+    ///     let (proj_dirs, redirections) = proj_dirs.redirect_unwritable(&[
+    ///         (DirKind::Cache, RedirectTarget::RuntimeDir),
+    ///         (DirKind::State, RedirectTarget::TempDir)
+    ///     ]);
+    ///     for redirection in &redirections {
+    ///         eprintln!("warning: {} is not writable, using {} instead; its contents will not persist",
+    ///                   redirection.original.display(), redirection.redirected.display());
+    ///     }
+    /// }
+    /// ```
+    pub fn redirect_unwritable(&self, targets: &[(DirKind, RedirectTarget)]) -> (ProjectDirs, Vec<Redirection>) {
+        redirect(self, targets, &|path| probe::nearest_existing(path).map_or(false, probe::is_writable))
+    }
+    fn set_dir(&mut self, kind: DirKind, path: Arc<Path>) {
+        match kind {
+            DirKind::Cache       => self.cache_dir        = path,
+            DirKind::Config      => self.config_dir       = path,
+            DirKind::ConfigLocal => self.config_local_dir = path,
+            DirKind::Data        => self.data_dir         = path,
+            DirKind::DataLocal   => self.data_local_dir   = path,
+            DirKind::Preference  => self.preference_dir   = path,
+            DirKind::Runtime     => self.runtime_dir      = Some(path),
+            DirKind::State       => self.state_dir        = Some(path),
+            _                    => {}
+        }
+    }
+}
+
+fn redirect(proj_dirs: &ProjectDirs, targets: &[(DirKind, RedirectTarget)], is_writable: &dyn Fn(&Path) -> bool) -> (ProjectDirs, Vec<Redirection>) {
+    let mut redirected   = proj_dirs.clone();
+    let mut redirections = Vec::new();
+    for &(kind, ref target) in targets {
+        let original = match proj_dirs.entries().iter().find(|&&(k, _)| k == kind).and_then(|&(_, dir)| dir) {
+            Some(original) if !is_writable(original) => original.to_path_buf(),
+            _                                        => continue
+        };
+        let path = match *target {
+            RedirectTarget::RuntimeDir    => runtime_dir(proj_dirs, kind, is_writable).or_else(|| temp_dir(proj_dirs, kind)),
+            RedirectTarget::TempDir       => temp_dir(proj_dirs, kind).or_else(|| runtime_dir(proj_dirs, kind, is_writable)),
+            RedirectTarget::Dir(ref path) => Some(path.clone())
+        };
+        let path = match path {
+            Some(path) => path,
+            None       => continue
+        };
+        redirected.set_dir(kind, Arc::from(path.as_path()));
+        redirections.push(Redirection { kind, original, redirected: path });
+    }
+    (redirected, redirections)
+}
+
+fn runtime_dir(proj_dirs: &ProjectDirs, kind: DirKind, is_writable: &dyn Fn(&Path) -> bool) -> Option<PathBuf> {
+    match proj_dirs.runtime_dir() {
+        Some(runtime_dir) if kind != DirKind::Runtime && is_writable(runtime_dir) => Some(runtime_dir.join(kind.name().to_ascii_lowercase())),
+        _                                                                        => None
+    }
+}
+
+// The temporary directory is shared by all users on Unix, so the name includes the user ID,
+// and the directory is only used if it is private to the current user.
+fn temp_dir(proj_dirs: &ProjectDirs, kind: DirKind) -> Option<PathBuf> {
+    let dir = temp_dir_parent(proj_dirs);
+    probe::create_private_dir(&dir).ok()?;
+    Some(dir.join(kind.name().to_ascii_lowercase()))
+}
+
+fn temp_dir_parent(proj_dirs: &ProjectDirs) -> PathBuf {
+    let project = proj_dirs.project_path().iter().map(|component| component.to_string_lossy()).collect::<Vec<_>>().join(".");
+    let name    = match probe::current_uid() {
+        Some(uid) => format!("{}-{}", project, uid),
+        None      => project
+    };
+    env::temp_dir().join(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use super::redirect;
+    use super::temp_dir_parent;
+    use DirKind;
+    use Platform;
+    use RedirectTarget;
+
+    #[test]
+    fn test_redirect_unwritable() {
+        let base_dirs     = Platform::Linux.base_dirs(Path::new("/home/alice"));
+        let mut proj_dirs = Platform::Linux.project(&base_dirs, "com", "Foo Corp", "Bar App").unwrap();
+        proj_dirs.runtime_dir = Some(Arc::from(Path::new("/run/user/1000/barapp")));
+        let read_only_home = |path: &Path| !path.starts_with("/home/alice");
+
+        let (redirected, redirections) = redirect(&proj_dirs, &[
+            (DirKind::Cache,   RedirectTarget::Dir(PathBuf::from("/var/tmp/barapp"))),
+            (DirKind::State,   RedirectTarget::RuntimeDir),
+            (DirKind::Runtime, RedirectTarget::RuntimeDir)
+        ], &read_only_home);
+        assert_eq!(redirections.len(), 2);
+        assert_eq!(redirections[0].original, Path::new("/home/alice/.cache/barapp"));
+        assert_eq!(redirected.cache_dir(),   Path::new("/var/tmp/barapp"));
+        assert_eq!(redirected.state_dir(),   Some(Path::new("/run/user/1000/barapp/state")));
+        assert_eq!(redirected.config_dir(),  proj_dirs.config_dir());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_temp_dir_is_private() {
+        use std::fs;
+        use std::os::unix::fs::symlink;
+        use std::os::unix::fs::PermissionsExt;
+
+        let base_dirs     = Platform::Linux.base_dirs(Path::new("/home/alice"));
+        let mut proj_dirs = Platform::Linux.project(&base_dirs, "", "", &format!("directories-redirect-{}", ::std::process::id())).unwrap();
+        let read_only_home = |path: &Path| !path.starts_with("/home/alice");
+        let targets        = [(DirKind::Cache, RedirectTarget::TempDir)];
+        let parent         = temp_dir_parent(&proj_dirs);
+
+        let (redirected, _) = redirect(&proj_dirs, &targets, &read_only_home);
+        assert_eq!(redirected.cache_dir(), parent.join("cache"));
+        assert_eq!(fs::metadata(&parent).unwrap().permissions().mode() & 0o777, 0o700);
+        fs::remove_dir(&parent).unwrap();
+
+        // A directory created by another user, or a symbolic link to one, must not be used.
+        let elsewhere = parent.with_extension("elsewhere");
+        fs::create_dir(&elsewhere).unwrap();
+        fs::set_permissions(&elsewhere, fs::Permissions::from_mode(0o700)).unwrap();
+        symlink(&elsewhere, &parent).unwrap();
+        let (redirected, redirections) = redirect(&proj_dirs, &targets, &read_only_home);
+        assert!(redirections.is_empty());
+        assert_eq!(redirected, proj_dirs);
+
+        proj_dirs.runtime_dir = Some(Arc::from(Path::new("/run/user/1000/barapp")));
+        let (redirected, _) = redirect(&proj_dirs, &targets, &read_only_home);
+        assert_eq!(redirected.cache_dir(), Path::new("/run/user/1000/barapp/cache"));
+        fs::remove_file(&parent).unwrap();
+
+        fs::create_dir(&parent).unwrap();
+        fs::set_permissions(&parent, fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(::probe::create_private_dir(&parent).is_err());
+        fs::set_permissions(&parent, fs::Permissions::from_mode(0o700)).unwrap();
+        assert!(::probe::create_private_dir(&parent).is_ok());
+        if ::probe::current_uid() == Some(0) {
+            let path = ::std::ffi::CString::new(parent.to_str().unwrap()).unwrap();
+            assert_eq!(unsafe { libc::chown(path.as_ptr(), 65534, 65534) }, 0);
+            assert!(::probe::create_private_dir(&parent).is_err());
+        }
+        fs::remove_dir(&parent).unwrap();
+        fs::remove_dir(&elsewhere).unwrap();
+    }
+}
@@ -0,0 +1,268 @@
+// Serialization uses the names of the getters as field names and writes `None` values, so that formats which are not
+// self-describing, e. g. bincode, can read every field back. Missing optional fields are deserialized as `None`.
+// Deserialization rejects relative paths, except for the project path, which is validated like in `ProjectDirs::try_from_path`.
+// `SymbolicPath` is serialized as a string in the form of its `Display` implementation.
+
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::de;
+use serde::ser::SerializeStruct;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+use validate_project_path;
+use BaseDirs;
+use ProjectDirs;
+use SymbolicPath;
+use UserDirs;
+
+#[derive(Deserialize)]
+struct BaseDirsRepr {
+    home_dir:         PathBuf,
+    cache_dir:        PathBuf,
+    config_dir:       PathBuf,
+    config_local_dir: PathBuf,
+    data_dir:         PathBuf,
+    data_local_dir:   PathBuf,
+    executable_dir:   Option<PathBuf>,
+    preference_dir:   PathBuf,
+    runtime_dir:      Option<PathBuf>,
+    state_dir:        Option<PathBuf>
+}
+
+#[derive(Deserialize)]
+struct UserDirsRepr {
+    home_dir:     PathBuf,
+    audio_dir:    Option<PathBuf>,
+    desktop_dir:  Option<PathBuf>,
+    document_dir: Option<PathBuf>,
+    download_dir: Option<PathBuf>,
+    font_dir:     Option<PathBuf>,
+    picture_dir:  Option<PathBuf>,
+    public_dir:   Option<PathBuf>,
+    template_dir: Option<PathBuf>,
+    video_dir:    Option<PathBuf>
+}
+
+#[derive(Deserialize)]
+struct ProjectDirsRepr {
+    project_path:     PathBuf,
+    cache_dir:        PathBuf,
+    config_dir:       PathBuf,
+    config_local_dir: PathBuf,
+    data_dir:         PathBuf,
+    data_local_dir:   PathBuf,
+    preference_dir:   PathBuf,
+    runtime_dir:      Option<PathBuf>,
+    state_dir:        Option<PathBuf>
+}
+
+impl Serialize for BaseDirs {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("BaseDirs", 10)?;
+        state.serialize_field("home_dir",         self.home_dir())?;
+        state.serialize_field("cache_dir",        self.cache_dir())?;
+        state.serialize_field("config_dir",       self.config_dir())?;
+        state.serialize_field("config_local_dir", self.config_local_dir())?;
+        state.serialize_field("data_dir",         self.data_dir())?;
+        state.serialize_field("data_local_dir",   self.data_local_dir())?;
+        state.serialize_field("executable_dir",   &self.executable_dir())?;
+        state.serialize_field("preference_dir",   self.preference_dir())?;
+        state.serialize_field("runtime_dir",      &self.runtime_dir())?;
+        state.serialize_field("state_dir",        &self.state_dir())?;
+        state.end()
+    }
+}
+
+impl Serialize for UserDirs {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("UserDirs", 10)?;
+        state.serialize_field("home_dir",     self.home_dir())?;
+        state.serialize_field("audio_dir",    &self.audio_dir())?;
+        state.serialize_field("desktop_dir",  &self.desktop_dir())?;
+        state.serialize_field("document_dir", &self.document_dir())?;
+        state.serialize_field("download_dir", &self.download_dir())?;
+        state.serialize_field("font_dir",     &self.font_dir())?;
+        state.serialize_field("picture_dir",  &self.picture_dir())?;
+        state.serialize_field("public_dir",   &self.public_dir())?;
+        state.serialize_field("template_dir", &self.template_dir())?;
+        state.serialize_field("video_dir",    &self.video_dir())?;
+        state.end()
+    }
+}
+
+impl Serialize for ProjectDirs {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ProjectDirs", 9)?;
+        state.serialize_field("project_path",     self.project_path())?;
+        state.serialize_field("cache_dir",        self.cache_dir())?;
+        state.serialize_field("config_dir",       self.config_dir())?;
+        state.serialize_field("config_local_dir", self.config_local_dir())?;
+        state.serialize_field("data_dir",         self.data_dir())?;
+        state.serialize_field("data_local_dir",   self.data_local_dir())?;
+        state.serialize_field("preference_dir",   self.preference_dir())?;
+        state.serialize_field("runtime_dir",      &self.runtime_dir())?;
+        state.serialize_field("state_dir",        &self.state_dir())?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for BaseDirs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BaseDirs, D::Error> {
+        let repr = BaseDirsRepr::deserialize(deserializer)?;
+        let base_dirs = BaseDirs {
+            home_dir:         absolute("home_dir",         repr.home_dir)?,
+            cache_dir:        absolute("cache_dir",        repr.cache_dir)?,
+            config_dir:       absolute("config_dir",       repr.config_dir)?,
+            config_local_dir: absolute("config_local_dir", repr.config_local_dir)?,
+            data_dir:         absolute("data_dir",         repr.data_dir)?,
+            data_local_dir:   absolute("data_local_dir",   repr.data_local_dir)?,
+            executable_dir:   optional_absolute("executable_dir", repr.executable_dir)?,
+            preference_dir:   absolute("preference_dir",   repr.preference_dir)?,
+            runtime_dir:      optional_absolute("runtime_dir",    repr.runtime_dir)?,
+            state_dir:        optional_absolute("state_dir",      repr.state_dir)?
+        };
+        Ok(base_dirs)
+    }
+}
+
+impl<'de> Deserialize<'de> for UserDirs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<UserDirs, D::Error> {
+        let repr = UserDirsRepr::deserialize(deserializer)?;
+        let user_dirs = UserDirs {
+            home_dir:     absolute("home_dir", repr.home_dir)?,
+            audio_dir:    optional_absolute("audio_dir",    repr.audio_dir)?,
+            desktop_dir:  optional_absolute("desktop_dir",  repr.desktop_dir)?,
+            document_dir: optional_absolute("document_dir", repr.document_dir)?,
+            download_dir: optional_absolute("download_dir", repr.download_dir)?,
+            font_dir:     optional_absolute("font_dir",     repr.font_dir)?,
+            picture_dir:  optional_absolute("picture_dir",  repr.picture_dir)?,
+            public_dir:   optional_absolute("public_dir",   repr.public_dir)?,
+            template_dir: optional_absolute("template_dir", repr.template_dir)?,
+            video_dir:    optional_absolute("video_dir",    repr.video_dir)?
+        };
+        Ok(user_dirs)
+    }
+}
+
+impl<'de> Deserialize<'de> for ProjectDirs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ProjectDirs, D::Error> {
+        let repr = ProjectDirsRepr::deserialize(deserializer)?;
+        validate_project_path(&repr.project_path).map_err(|e| invalid_value("project_path", &repr.project_path, e))?;
+        let project_dirs = ProjectDirs {
+            project_path:     repr.project_path.into(),
+            cache_dir:        absolute("cache_dir",        repr.cache_dir)?,
+            config_dir:       absolute("config_dir",       repr.config_dir)?,
+            config_local_dir: absolute("config_local_dir", repr.config_local_dir)?,
+            data_dir:         absolute("data_dir",         repr.data_dir)?,
+            data_local_dir:   absolute("data_local_dir",   repr.data_local_dir)?,
+            preference_dir:   absolute("preference_dir",   repr.preference_dir)?,
+            runtime_dir:      optional_absolute("runtime_dir", repr.runtime_dir)?,
+            state_dir:        optional_absolute("state_dir",   repr.state_dir)?
+        };
+        Ok(project_dirs)
+    }
+}
+
+impl Serialize for SymbolicPath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SymbolicPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<SymbolicPath, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(|error| de::Error::custom(format_args!("invalid symbolic path {:?}: {}", text, error)))
+    }
+}
+
+fn absolute<E: de::Error>(key: &'static str, path: PathBuf) -> Result<Arc<Path>, E> {
+    if path.is_absolute() {
+        Ok(path.into())
+    } else {
+        Err(invalid_value(key, &path, "path is not absolute"))
+    }
+}
+
+fn optional_absolute<E: de::Error>(key: &'static str, path: Option<PathBuf>) -> Result<Option<Arc<Path>>, E> {
+    path.map(|path| absolute(key, path)).transpose()
+}
+
+fn invalid_value<E: de::Error, M: fmt::Display>(key: &'static str, path: &Path, message: M) -> E {
+    E::custom(format_args!("invalid value {:?} for `{}`: {}", path, key, message))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    #[test]
+    fn test_round_trip() {
+        if let Some(base_dirs) = ::BaseDirs::new() {
+            let json = serde_json::to_string(&base_dirs).unwrap();
+            let deserialized: ::BaseDirs = serde_json::from_str(&json).unwrap();
+            assert_eq!(base_dirs, deserialized);
+        }
+        if let Some(proj_dirs) = ::ProjectDirs::from("com", "Foo Corp", "Bar App") {
+            let json = serde_json::to_string(&proj_dirs).unwrap();
+            assert!(json.contains("\"project_path\":") && json.contains("\"config_dir\":"));
+            let deserialized: ::ProjectDirs = serde_json::from_str(&json).unwrap();
+            assert_eq!(proj_dirs, deserialized);
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_rejects_relative_paths() {
+        let user_dirs = r#"{ "home_dir": "/home/alice", "audio_dir": "/home/alice/Music" }"#;
+        assert!(serde_json::from_str::<::UserDirs>(user_dirs).is_ok());
+        let user_dirs = r#"{ "home_dir": "/home/alice", "audio_dir": "Music" }"#;
+        let error = serde_json::from_str::<::UserDirs>(user_dirs).unwrap_err().to_string();
+        assert!(error.contains("audio_dir"), "{}", error);
+
+        let proj_dirs = r#"{ "project_path": "../barapp", "cache_dir": "/c", "config_dir": "/c", "config_local_dir": "/c",
+                             "data_dir": "/d", "data_local_dir": "/d", "preference_dir": "/c" }"#;
+        let error = serde_json::from_str::<::ProjectDirs>(proj_dirs).unwrap_err().to_string();
+        assert!(error.contains("project_path"), "{}", error);
+    }
+
+    #[test]
+    fn test_round_trip_none_fields() {
+        extern crate bincode;
+
+        use std::path::Path;
+        use std::sync::Arc;
+
+        let mut base_dirs = ::Platform::Linux.base_dirs(Path::new("/home/alice"));
+        base_dirs.runtime_dir = None;
+        base_dirs.state_dir   = Some(Arc::from(Path::new("/home/alice/.local/state")));
+        let bytes = bincode::serialize(&base_dirs).unwrap();
+        assert_eq!(bincode::deserialize::<::BaseDirs>(&bytes).unwrap(), base_dirs);
+
+        let user_dirs = ::Platform::Windows.user_dirs(Path::new("/Users/Alice"));
+        let bytes = bincode::serialize(&user_dirs).unwrap();
+        assert_eq!(bincode::deserialize::<::UserDirs>(&bytes).unwrap(), user_dirs);
+
+        let proj_dirs = ::Platform::MacOs.project(&::Platform::MacOs.base_dirs(Path::new("/Users/Alice")), "com", "Foo Corp", "Bar App").unwrap();
+        assert_eq!(proj_dirs.state_dir(), None);
+        let bytes = bincode::serialize(&proj_dirs).unwrap();
+        assert_eq!(bincode::deserialize::<::ProjectDirs>(&bytes).unwrap(), proj_dirs);
+
+        let json = serde_json::to_string(&proj_dirs).unwrap();
+        assert!(json.contains("\"state_dir\":null"), "{}", json);
+        assert_eq!(serde_json::from_str::<::ProjectDirs>(&json).unwrap(), proj_dirs);
+    }
+
+    #[test]
+    fn test_symbolic_path() {
+        let path: ::SymbolicPath = serde_json::from_str(r#""{Data}/plugins/x""#).unwrap();
+        assert_eq!(serde_json::to_string(&path).unwrap(), r#""{Data}/plugins/x""#);
+        assert!(serde_json::from_str::<::SymbolicPath>(r#""{Datum}/x""#).is_err());
+    }
+}
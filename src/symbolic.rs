@@ -0,0 +1,227 @@
+use std::error;
+use std::fmt;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use classify::classify;
+use DirKind;
+use DirLookup;
+use Platform;
+
+/// `SymbolicPath` stores a path relative to a directory identified by a [`DirKind`], e. g. `{Document}/projects`.
+///
+/// Unlike absolute paths, symbolic paths remain valid when a config file is moved to another user or another platform:
+/// they are converted from absolute paths with [`SymbolicPath::from_path`], and back with [`SymbolicPath::resolve`]
+/// against the `BaseDirs`, `UserDirs` or `ProjectDirs` struct, or the [`Snapshot`](struct.Snapshot.html), of the current machine.
+///
+/// The relative part consists of components which are valid Unicode, separated by `/` on all platforms.
+/// With the `serde` feature, `SymbolicPath` is serialized as a string in the form returned by its `Display` implementation.
+///
+/// # Examples
+///
+/// ```
+/// use directories::{DirKind, SymbolicPath, UserDirs};
+///
+/// let path: SymbolicPath = "{Document}/projects".parse().unwrap();
+/// assert_eq!(path.kind(), DirKind::Document);
+/// if let Some(user_dirs) = UserDirs::new() {
+///     path.resolve(&user_dirs);
+///     // Linux:   Some(/home/alice/Documents/projects)
+///     // Windows: Some(C:\Users\Alice\Documents\projects)
+///     // macOS:   Some(/Users/Alice/Documents/projects)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolicPath {
+    kind:     DirKind,
+    relative: String
+}
+
+/// The reason why a symbolic path could not be created or parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolicPathError {
+    /// The text does not start with a directory kind in braces, e. g. `{Data}`, followed by the end of the text or a `/`.
+    MalformedKind,
+    /// The name in braces is not the name of a [`DirKind`].
+    UnknownKind,
+    /// The relative path is absolute or has a prefix.
+    AbsolutePath,
+    /// The relative path contains a `..` component.
+    ParentDirComponent,
+    /// The relative path is not valid Unicode.
+    NonUnicodePath,
+    /// A component of the relative path contains a `\`, which is a separator in the string form of symbolic paths and on Windows.
+    BackslashInComponent,
+    /// A component of the relative path contains a character which is not allowed in file names on Windows,
+    /// e. g. the `:` of a drive prefix like `C:`.
+    ReservedCharacter,
+    /// The path is not located in any of the directories it was compared with.
+    OutsideKnownDirs
+}
+
+impl SymbolicPath {
+    /// Creates a symbolic path from a kind of directory and a path relative to it.
+    ///
+    /// `.` components are removed; absolute paths, `..` components and components which are not valid Unicode are rejected.
+    /// Components containing a `\`, which is a valid character in file names on Unix, are rejected as well,
+    /// as they could not be parsed back from the string form, and so are components containing a character
+    /// which is not allowed in file names on Windows (`<`, `>`, `:`, `"`, `|`, `?`, `*` and control characters),
+    /// as they could be read as a drive prefix when the path is resolved there.
+    pub fn new(kind: DirKind, relative: &Path) -> Result<SymbolicPath, SymbolicPathError> {
+        let mut components = Vec::new();
+        for component in relative.components() {
+            match component {
+                Component::Prefix(_) | Component::RootDir => return Err(SymbolicPathError::AbsolutePath),
+                Component::ParentDir                      => return Err(SymbolicPathError::ParentDirComponent),
+                Component::CurDir                         => {},
+                Component::Normal(name)                   => match name.to_str() {
+                    Some(name) if name.contains('\\')              => return Err(SymbolicPathError::BackslashInComponent),
+                    Some(name) if name.contains(is_reserved_char) => return Err(SymbolicPathError::ReservedCharacter),
+                    Some(name)                                     => components.push(name),
+                    None                                           => return Err(SymbolicPathError::NonUnicodePath)
+                }
+            }
+        }
+        Ok(SymbolicPath { kind, relative: components.join("/") })
+    }
+    /// Converts an absolute path into its symbolic form, relative to the most specific directory of `dirs` which contains it.
+    ///
+    /// Directories are matched like in [`BaseDirs::classify`](struct.BaseDirs.html#method.classify).
+    pub fn from_path<D: DirLookup + ?Sized>(dirs: &D, path: &Path) -> Result<SymbolicPath, SymbolicPathError> {
+        let entries: Vec<(DirKind, Option<&Path>)> = DirKind::ALL.iter().map(|&kind| (kind, dirs.dir(kind))).collect();
+        match classify(&entries, path, Platform::current()) {
+            Some((kind, relative)) => SymbolicPath::new(kind, &relative),
+            None                   => Err(SymbolicPathError::OutsideKnownDirs)
+        }
+    }
+    /// Returns the kind of the directory the path is relative to.
+    pub fn kind(&self) -> DirKind {
+        self.kind
+    }
+    /// Returns the relative part of the path, with components separated by `/`, e. g. `"plugins/x"` for `{Data}/plugins/x`.
+    pub fn relative(&self) -> &str {
+        &self.relative
+    }
+    /// Returns the absolute path this symbolic path refers to in `dirs`,
+    /// or `None` if `dirs` does not provide a directory of the kind of this path.
+    pub fn resolve<D: DirLookup + ?Sized>(&self, dirs: &D) -> Option<PathBuf> {
+        let mut path = dirs.dir(self.kind)?.to_path_buf();
+        for component in Path::new(&self.relative).components() {
+            if let Component::Normal(name) = component {
+                path.push(name);
+            }
+        }
+        Some(path)
+    }
+}
+
+impl FromStr for SymbolicPath {
+    type Err = SymbolicPathError;
+
+    /// Parses a symbolic path in the form `{Kind}` or `{Kind}/relative/path`.
+    ///
+    /// Both `/` and `\` are accepted as separators in the relative part,
+    /// whose components are validated like in [`SymbolicPath::new`].
+    fn from_str(text: &str) -> Result<SymbolicPath, SymbolicPathError> {
+        if !text.starts_with('{') {
+            return Err(SymbolicPathError::MalformedKind);
+        }
+        let end  = text.find('}').ok_or(SymbolicPathError::MalformedKind)?;
+        let kind = DirKind::from_name(&text[1..end]).ok_or(SymbolicPathError::UnknownKind)?;
+        let rest = &text[end + 1..];
+        if !rest.is_empty() && !rest.starts_with(['/', '\\']) {
+            return Err(SymbolicPathError::MalformedKind);
+        }
+        let components: Vec<&str> = rest.split(['/', '\\']).filter(|name| !name.is_empty()).collect();
+        SymbolicPath::new(kind, Path::new(&components.join("/")))
+    }
+}
+
+fn is_reserved_char(c: char) -> bool {
+    matches!(c, '<' | '>' | ':' | '"' | '|' | '?' | '*') || c.is_control()
+}
+
+impl fmt::Display for SymbolicPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.relative.is_empty() {
+            write!(f, "{{{}}}", self.kind)
+        } else {
+            write!(f, "{{{}}}/{}", self.kind, self.relative)
+        }
+    }
+}
+
+impl fmt::Display for SymbolicPathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            SymbolicPathError::MalformedKind        => "symbolic path does not start with a directory kind in braces",
+            SymbolicPathError::UnknownKind          => "unknown directory kind",
+            SymbolicPathError::AbsolutePath         => "relative path is absolute",
+            SymbolicPathError::ParentDirComponent   => "relative path contains a `..` component",
+            SymbolicPathError::NonUnicodePath       => "relative path is not valid Unicode",
+            SymbolicPathError::BackslashInComponent => "relative path contains a `\\` in a component",
+            SymbolicPathError::ReservedCharacter    => "relative path contains a character which is not allowed on Windows",
+            SymbolicPathError::OutsideKnownDirs     => "path is not located in a known directory"
+        };
+        f.write_str(message)
+    }
+}
+
+impl error::Error for SymbolicPathError {}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use DirKind;
+    use Platform;
+    use SymbolicPath;
+    use SymbolicPathError;
+
+    #[test]
+    fn test_parse_and_display() {
+        let path: SymbolicPath = "{Data}\\plugins//./x/".parse().unwrap();
+        assert_eq!(path.kind(), DirKind::Data);
+        assert_eq!(path.relative(), "plugins/x");
+        assert_eq!(path.to_string(), "{Data}/plugins/x");
+        assert_eq!("{Home}".parse::<SymbolicPath>().unwrap().to_string(), "{Home}");
+
+        assert_eq!("Data/x".parse::<SymbolicPath>(),            Err(SymbolicPathError::MalformedKind));
+        assert_eq!("{Data}x".parse::<SymbolicPath>(),           Err(SymbolicPathError::MalformedKind));
+        assert_eq!("{Datum}/x".parse::<SymbolicPath>(),         Err(SymbolicPathError::UnknownKind));
+        assert_eq!("{Data}/../x".parse::<SymbolicPath>(),       Err(SymbolicPathError::ParentDirComponent));
+        assert_eq!("{Data}/C:x".parse::<SymbolicPath>(),        Err(SymbolicPathError::ReservedCharacter));
+        assert_eq!("{Data}/C:/Windows".parse::<SymbolicPath>(), Err(SymbolicPathError::ReservedCharacter));
+        assert_eq!(SymbolicPath::new(DirKind::Data, Path::new("a?b")), Err(SymbolicPathError::ReservedCharacter));
+    }
+
+    #[test]
+    fn test_resolve_and_from_path() {
+        let linux_dirs   = Platform::Linux.user_dirs(Path::new("/home/alice"));
+        let windows_dirs = Platform::Windows.user_dirs(Path::new("/Users/Alice"));
+
+        let path = SymbolicPath::from_path(&linux_dirs, Path::new("/home/alice/Documents/projects/x")).unwrap();
+        assert_eq!(path.to_string(), "{Document}/projects/x");
+        assert_eq!(path.resolve(&windows_dirs), Some(["/Users/Alice", "Documents", "projects", "x"].iter().collect()));
+        assert_eq!(path.resolve(&Platform::Linux.base_dirs(Path::new("/home/alice"))), None);
+
+        assert_eq!(SymbolicPath::from_path(&linux_dirs, Path::new("/etc/hosts")), Err(SymbolicPathError::OutsideKnownDirs));
+        assert_eq!(SymbolicPath::new(DirKind::Cache, Path::new("/x")), Err(SymbolicPathError::AbsolutePath));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = SymbolicPath::new(DirKind::Data, Path::new("plugins/./x y")).unwrap();
+        assert_eq!(path.to_string().parse(), Ok(path));
+
+        // On Unix, `\` is part of the file name, but it is a separator in the string form.
+        if cfg!(unix) {
+            assert_eq!(SymbolicPath::new(DirKind::Data, Path::new("plugins/a\\b")), Err(SymbolicPathError::BackslashInComponent));
+        } else {
+            let path = SymbolicPath::new(DirKind::Data, Path::new("plugins\\a")).unwrap();
+            assert_eq!(path.to_string().parse(), Ok(path));
+        }
+    }
+}
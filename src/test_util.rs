@@ -0,0 +1,42 @@
+// Helpers shared by the tests of several modules.
+
+use std::env;
+use std::fs;
+use std::ops::Deref;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+// A path in the temporary directory, unique to this process and call, which is not created,
+// but removed with its contents on drop, so that a failing assertion does not leave it behind.
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+    pub(crate) fn new(name: &str) -> TempDir {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        TempDir(env::temp_dir().join(format!("directories-{}-{}-{}", name, process::id(), COUNTER.fetch_add(1, Ordering::Relaxed))))
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -9,4 +9,4 @@ use ProjectDirs;
 pub fn base_dirs() -> Option<BaseDirs> { None }
 pub fn user_dirs() -> Option<UserDirs> { None }
 pub fn project_dirs_from_path(project_path: PathBuf) -> Option<ProjectDirs> { None }
-pub fn project_dirs_from(qualifier: &str, organization: &str, application: &str) -> Option<ProjectDirs> { None }
+pub fn project_path_from(qualifier: &str, organization: &str, application: &str) -> PathBuf { PathBuf::from(application) }
@@ -1,9 +1,11 @@
 extern crate dirs_sys;
 
 use std::path::PathBuf;
-use std::iter::FromIterator;
+use std::sync::Arc;
 
+use platform::windows_project_dirs;
 use BaseDirs;
+use Platform;
 use UserDirs;
 use ProjectDirs;
 
@@ -18,14 +20,14 @@ pub fn base_dirs() -> Option<BaseDirs> {
         let preference_dir   = data_dir.clone();
 
         let base_dirs = BaseDirs {
-            home_dir:         home_dir,
-            cache_dir:        cache_dir,
-            config_dir:       config_dir,
-            config_local_dir: config_local_dir,
-            data_dir:         data_dir,
-            data_local_dir:   data_local_dir,
+            home_dir:         home_dir.into(),
+            cache_dir:        cache_dir.into(),
+            config_dir:       config_dir.into(),
+            config_local_dir: config_local_dir.into(),
+            data_dir:         data_dir.into(),
+            data_local_dir:   data_local_dir.into(),
             executable_dir:   None,
-            preference_dir:   preference_dir,
+            preference_dir:   preference_dir.into(),
             runtime_dir:      None,
             state_dir:        None
         };
@@ -47,16 +49,16 @@ pub fn user_dirs() -> Option<UserDirs> {
         let video_dir     = dirs_sys::known_folder_videos();
 
         let user_dirs = UserDirs {
-            home_dir:     home_dir,
-            audio_dir:    audio_dir,
-            desktop_dir:  desktop_dir,
-            document_dir: document_dir,
-            download_dir: download_dir,
+            home_dir:     home_dir.into(),
+            audio_dir:    audio_dir.map(Arc::from),
+            desktop_dir:  desktop_dir.map(Arc::from),
+            document_dir: document_dir.map(Arc::from),
+            download_dir: download_dir.map(Arc::from),
             font_dir:     None,
-            picture_dir:  picture_dir,
-            public_dir:   public_dir,
-            template_dir: template_dir,
-            video_dir:    video_dir
+            picture_dir:  picture_dir.map(Arc::from),
+            public_dir:   public_dir.map(Arc::from),
+            template_dir: template_dir.map(Arc::from),
+            video_dir:    video_dir.map(Arc::from)
         };
         Some(user_dirs)
     } else {
@@ -68,33 +70,12 @@ pub fn project_dirs_from_path(project_path: PathBuf) -> Option<ProjectDirs> {
     let app_data_local   = dirs_sys::known_folder_local_app_data();
     let app_data_roaming = dirs_sys::known_folder_roaming_app_data();
     if let (Some(app_data_local), Some(app_data_roaming)) = (app_data_local, app_data_roaming) {
-        let app_data_local   = app_data_local.join(&project_path);
-        let app_data_roaming = app_data_roaming.join(&project_path);
-        let cache_dir        = app_data_local.join("cache");
-        let data_local_dir   = app_data_local.join("data");
-        let config_dir       = app_data_roaming.join("config");
-        let config_local_dir = app_data_local.join("config");
-        let data_dir         = app_data_roaming.join("data");
-        let preference_dir   = config_dir.clone();
-
-        let project_dirs = ProjectDirs {
-            project_path:     project_path,
-            cache_dir:        cache_dir,
-            config_dir:       config_dir,
-            config_local_dir: config_local_dir,
-            data_dir:         data_dir,
-            data_local_dir:   data_local_dir,
-            preference_dir:   preference_dir,
-            runtime_dir:      None,
-            state_dir:        None
-        };
-        Some(project_dirs)
+        Some(windows_project_dirs(&app_data_local, &app_data_roaming, project_path))
     } else {
         None
     }
-
 }
 
-pub fn project_dirs_from(_qualifier: &str, organization: &str, application: &str) -> Option<ProjectDirs> {
-    ProjectDirs::from_path(PathBuf::from_iter(&[organization, application]))
+pub fn project_path_from(qualifier: &str, organization: &str, application: &str) -> PathBuf {
+    Platform::Windows.project_path(qualifier, organization, application)
 }